@@ -0,0 +1,182 @@
+use crate::{registry::Day, toml_file::TomlFile};
+use anyhow::Context;
+use std::{
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+};
+
+/// Name of the answers file, in the corpus directory.
+pub const FILE: &str = "answers.toml";
+
+/// Confirmed answers, keyed by day, input name and part.
+///
+/// ```toml
+/// [day5.default]
+/// part1 = "RTGWZTHLD"
+/// part2 = "STHGRZZFR"
+/// ```
+pub struct Answers(TomlFile);
+
+impl Answers {
+    /// Loads the answers file, a missing file simply has no known answers.
+    pub fn load(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        TomlFile::load(path, "answers file").map(Self)
+    }
+
+    pub fn path(&self) -> &Path {
+        self.0.path()
+    }
+
+    pub fn get(&self, day: u8, input: &str, part: u8) -> Option<&str> {
+        self.0
+            .document
+            .get(&format!("day{day}"))?
+            .get(input)?
+            .get(format!("part{part}"))?
+            .as_str()
+    }
+
+    /// Records a confirmed answer, call [`Answers::save`] to persist it.
+    pub fn record(&mut self, day: u8, input: &str, part: u8, answer: &str) -> anyhow::Result<()> {
+        let input = self
+            .0
+            .day_table_mut(day)?
+            .entry(input)
+            .or_insert_with(toml_edit::table)
+            .as_table_mut()
+            .context("Answers of an input must be a table")?;
+        input.insert(&format!("part{part}"), toml_edit::value(answer));
+        Ok(())
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        self.0.save()
+    }
+}
+
+/// Outcome of checking a single part against the known answers.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    Error(String),
+    /// No confirmed answer yet, holds the computed one.
+    Unknown(String),
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected, actual }
+                if expected.contains('\n') || actual.contains('\n') =>
+            {
+                write!(f, "FAIL expected\n{expected}\ngot\n{actual}")
+            }
+            Status::Fail { expected, actual } => {
+                write!(f, "FAIL expected {expected}, got {actual}")
+            }
+            Status::Error(error) => write!(f, "FAIL {error}"),
+            Status::Unknown(actual) if actual.contains('\n') => write!(f, "unknown, got\n{actual}"),
+            Status::Unknown(actual) => write!(f, "unknown, got {actual}"),
+        }
+    }
+}
+
+/// Solves every part of `day` and compares them to the known answers for the named input.
+pub fn verify(day: &Day, name: &str, input: &str, answers: &Answers) -> Vec<(u8, Status)> {
+    let parts: Vec<u8> = (1..=day.puzzle.parts()).collect();
+    let run = match day.puzzle.solve(input, &parts) {
+        Ok(run) => run,
+        Err(error) => {
+            let error = format!("{error:#}");
+            return parts
+                .into_iter()
+                .map(|part| (part, Status::Error(error.clone())))
+                .collect();
+        }
+    };
+
+    run.answers
+        .into_iter()
+        .map(|answer| {
+            let status = match (answer.value, answers.get(day.day, name, answer.part)) {
+                (Err(error), _) => Status::Error(format!("{error:#}")),
+                (Ok(actual), None) => Status::Unknown(actual),
+                (Ok(actual), Some(expected)) if actual == expected => Status::Pass,
+                (Ok(actual), Some(expected)) => Status::Fail {
+                    expected: expected.to_owned(),
+                    actual,
+                },
+            };
+            (answer.part, status)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, Status};
+    use crate::{corpus, registry};
+    use std::path::Path;
+
+    /// Runs every registered day over every input of the corpus.
+    #[test]
+    fn test_corpus() -> anyhow::Result<()> {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(corpus::DIR);
+        let answers = Answers::load(dir.join(super::FILE))?;
+
+        let mut failures = Vec::new();
+        for input in corpus::inputs(&dir)? {
+            let Some(day) = registry::find(input.day) else {
+                failures.push(format!(
+                    "day{}/{}: day is not registered",
+                    input.day, input.name
+                ));
+                continue;
+            };
+            for (part, status) in super::verify(day, &input.name, &input.read()?, &answers) {
+                if status != Status::Pass {
+                    failures.push(format!(
+                        "day{}/{} part {part}: {status}",
+                        day.day, input.name
+                    ));
+                }
+            }
+        }
+
+        assert!(
+            failures.is_empty(),
+            "Failing inputs:\n{}",
+            failures.join("\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_record() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("answers.toml");
+
+        let mut answers = Answers::load(&path)?;
+        assert_eq!(answers.get(5, "alice", 1), None);
+        answers.record(5, "alice", 1, "CMZ")?;
+        answers.record(5, "alice", 2, "MCD")?;
+        answers.save()?;
+
+        let answers = Answers::load(&path)?;
+        assert_eq!(answers.get(5, "alice", 1), Some("CMZ"));
+        assert_eq!(answers.get(5, "alice", 2), Some("MCD"));
+        assert_eq!(answers.get(5, "bob", 2), None);
+        assert_eq!(
+            std::fs::read_to_string(&path)?,
+            "[day5.alice]\npart1 = \"CMZ\"\npart2 = \"MCD\"\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{registry::Day, toml_file::TomlFile};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use toml_edit::InlineTable;
+
+/// Default name of the saved results, at the root of the workspace.
+pub const FILE: &str = "bench.toml";
+
+/// Summary of the timings of a single phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarizes a non empty set of samples, percentiles use the nearest rank.
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let percentile = |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).max(1) - 1];
+
+        Self {
+            min: sorted[0],
+            median: percentile(0.5),
+            p95: percentile(0.95),
+        }
+    }
+
+    /// Relative change of the median compared to `baseline`, positive when slower.
+    pub fn change(&self, baseline: &Stats) -> f64 {
+        self.median.as_secs_f64() / baseline.median.as_secs_f64() - 1.0
+    }
+}
+
+/// Timings of one phase of a day, `phase` being either `parse`, `part1` or `part2`.
+pub struct Measurement {
+    pub day: u8,
+    pub phase: String,
+    pub stats: Stats,
+}
+
+pub fn measure(
+    day: &Day,
+    input: &str,
+    warmup: usize,
+    iterations: usize,
+) -> anyhow::Result<Vec<Measurement>> {
+    let samples = day.puzzle.bench(input, warmup, iterations)?;
+
+    let mut measurements = vec![Measurement {
+        day: day.day,
+        phase: "parse".to_owned(),
+        stats: Stats::new(&samples.parse),
+    }];
+    measurements.extend(samples.parts.iter().map(|(part, samples)| Measurement {
+        day: day.day,
+        phase: format!("part{part}"),
+        stats: Stats::new(samples),
+    }));
+    Ok(measurements)
+}
+
+/// Previously saved results, keyed by day and phase, in nanoseconds.
+///
+/// ```toml
+/// [day1]
+/// parse = { min = 520125, median = 551650, p95 = 612004 }
+/// ```
+pub struct Baseline(TomlFile);
+
+impl Baseline {
+    /// Loads saved results, a missing file simply has no baseline.
+    pub fn load(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        TomlFile::load(path, "benchmark results").map(Self)
+    }
+
+    pub fn path(&self) -> &Path {
+        self.0.path()
+    }
+
+    pub fn get(&self, day: u8, phase: &str) -> Option<Stats> {
+        let stats = self.0.document.get(&format!("day{day}"))?.get(phase)?;
+        let nanos = |key: &str| Some(Duration::from_nanos(stats.get(key)?.as_integer()? as u64));
+
+        Some(Stats {
+            min: nanos("min")?,
+            median: nanos("median")?,
+            p95: nanos("p95")?,
+        })
+    }
+
+    pub fn record(&mut self, measurement: &Measurement) -> anyhow::Result<()> {
+        let day = self.0.day_table_mut(measurement.day)?;
+
+        let mut stats = InlineTable::new();
+        for (key, duration) in [
+            ("min", measurement.stats.min),
+            ("median", measurement.stats.median),
+            ("p95", measurement.stats.p95),
+        ] {
+            stats.insert(key, (duration.as_nanos() as i64).into());
+        }
+        day.insert(&measurement.phase, toml_edit::value(stats));
+        Ok(())
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        self.0.save()
+    }
+}
+
+/// Prints the measurements as an aligned table, comparing each median to the baseline. Returns
+/// the number of regressions found.
+pub fn print_table(measurements: &[Measurement], baseline: &Baseline, threshold: f64) -> usize {
+    let mut regressions = 0;
+
+    println!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  Baseline",
+        "Day", "Phase", "Min", "Median", "P95"
+    );
+    for Measurement { day, phase, stats } in measurements {
+        let comparison = match baseline.get(*day, phase) {
+            Some(previous) => {
+                let change = stats.change(&previous);
+                if change > threshold {
+                    regressions += 1;
+                    format!("{:+.1}% REGRESSION", change * 100.0)
+                } else {
+                    format!("{:+.1}%", change * 100.0)
+                }
+            }
+            None => "-".to_owned(),
+        };
+        println!(
+            "{day:>3}  {phase:<5}  {:>10}  {:>10}  {:>10}  {comparison}",
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.p95),
+        );
+    }
+
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Baseline, Measurement, Stats};
+    use std::time::Duration;
+
+    fn stats(min: u64, median: u64, p95: u64) -> Stats {
+        Stats {
+            min: Duration::from_micros(min),
+            median: Duration::from_micros(median),
+            p95: Duration::from_micros(p95),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples: Vec<_> = (1..=20).rev().map(Duration::from_micros).collect();
+        assert_eq!(Stats::new(&samples), stats(1, 10, 19));
+        assert_eq!(Stats::new(&samples[..1]), stats(20, 20, 20));
+
+        let baseline = stats(1, 100, 200);
+        assert!((stats(1, 125, 150).change(&baseline) - 0.25).abs() < 1e-9);
+        assert!((stats(1, 80, 300).change(&baseline) + 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_baseline() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("bench.toml");
+
+        let mut baseline = Baseline::load(&path)?;
+        assert_eq!(baseline.get(3, "part1"), None);
+        baseline.record(&Measurement {
+            day: 3,
+            phase: "part1".to_owned(),
+            stats: stats(5, 7, 9),
+        })?;
+        baseline.save()?;
+        assert_eq!(
+            std::fs::read_to_string(&path)?,
+            "[day3]\npart1 = { min = 5000, median = 7000, p95 = 9000 }\n"
+        );
+
+        let baseline = Baseline::load(&path)?;
+        assert_eq!(baseline.get(3, "part1"), Some(stats(5, 7, 9)));
+        assert_eq!(baseline.get(3, "part2"), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use crate::corpus;
+use anyhow::{anyhow, bail, Context};
+use std::{
+    env,
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use toml_edit::Document;
+
+/// Name of the optional settings file, at the root of the workspace.
+pub const FILE: &str = "aoc.toml";
+
+/// Website serving the puzzles.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Minimum delay between two requests to the website.
+pub const RATE_LIMIT: Duration = Duration::from_secs(10);
+
+/// Longest a single solve may run before the runner gives up on it.
+pub const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Runner settings read from `aoc.toml`, each of them can be overridden by an environment
+/// variable. Relative paths in the file are relative to the root of the workspace.
+///
+/// ```toml
+/// inputs = "../private-inputs"
+/// session = "53616c746564..."
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct Config {
+    /// Directory holding the puzzle inputs and their answers, overridden by `AOC_INPUTS`.
+    pub inputs: PathBuf,
+    /// Session cookie of a logged in user, overridden by `AOC_SESSION`.
+    pub session: Option<String>,
+    /// Website serving the puzzles, overridden by `AOC_BASE_URL`.
+    pub base_url: String,
+    /// Minimum delay between two requests, as `rate_limit` seconds or `AOC_RATE_LIMIT`.
+    pub rate_limit: Duration,
+    /// Wall clock limit of a single solve or explanation, as `timeout` seconds or `AOC_TIMEOUT`,
+    /// at least one second.
+    pub timeout: Duration,
+}
+
+impl Config {
+    /// Loads the settings of the workspace, a missing file keeps every default.
+    pub fn load(root: &Path) -> anyhow::Result<Self> {
+        let path = root.join(FILE);
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error.into()),
+        };
+        Self::parse(root, &contents, |name| env::var_os(name))
+            .with_context(|| format!("Invalid settings {}", path.display()))
+    }
+
+    fn parse(
+        root: &Path,
+        contents: &str,
+        var: impl Fn(&str) -> Option<OsString>,
+    ) -> anyhow::Result<Self> {
+        let document: Document = contents.parse()?;
+        let setting = |key: &str, variable: &str| -> anyhow::Result<Option<String>> {
+            if let Some(value) = var(variable) {
+                return value
+                    .into_string()
+                    .map(Some)
+                    .map_err(|_| anyhow!("{variable} is not valid unicode"));
+            }
+            document
+                .get(key)
+                .map(|item| {
+                    item.as_str()
+                        .map(str::to_owned)
+                        .with_context(|| format!("`{key}` must be a string"))
+                })
+                .transpose()
+        };
+
+        let inputs = match (var("AOC_INPUTS"), document.get("inputs")) {
+            (Some(path), _) => PathBuf::from(path),
+            (None, Some(path)) => root.join(path.as_str().context("`inputs` must be a string")?),
+            (None, None) => root.join(corpus::DIR),
+        };
+        let seconds = |key: &str, variable: &str, default: Duration| -> anyhow::Result<Duration> {
+            let seconds = match (var(variable), document.get(key)) {
+                (Some(seconds), _) => seconds
+                    .to_str()
+                    .and_then(|seconds| seconds.parse().ok())
+                    .with_context(|| format!("{variable} must be a number of seconds"))?,
+                (None, Some(seconds)) => seconds
+                    .as_integer()
+                    .and_then(|seconds| seconds.try_into().ok())
+                    .with_context(|| format!("`{key}` must be a number of seconds"))?,
+                (None, None) => return Ok(default),
+            };
+            Ok(Duration::from_secs(seconds))
+        };
+
+        let timeout = seconds("timeout", "AOC_TIMEOUT", TIMEOUT)?;
+        if timeout.is_zero() {
+            bail!("The timeout must be at least one second");
+        }
+
+        Ok(Self {
+            inputs,
+            session: setting("session", "AOC_SESSION")?,
+            base_url: setting("base_url", "AOC_BASE_URL")?.unwrap_or_else(|| BASE_URL.to_owned()),
+            rate_limit: seconds("rate_limit", "AOC_RATE_LIMIT", RATE_LIMIT)?,
+            timeout,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+    use std::{path::Path, time::Duration};
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        let root = Path::new("/aoc");
+
+        let config = Config::parse(root, "", |_| None)?;
+        assert_eq!(config.inputs, Path::new("/aoc/inputs"));
+        assert_eq!(config.session, None);
+        assert_eq!(config.base_url, super::BASE_URL);
+        assert_eq!(config.rate_limit, super::RATE_LIMIT);
+        assert_eq!(config.timeout, super::TIMEOUT);
+
+        let settings =
+            "inputs = \"../private\"\nsession = \"cookie\"\nrate_limit = 60\ntimeout = 5";
+        let config = Config::parse(root, settings, |_| None)?;
+        assert_eq!(config.inputs, Path::new("/aoc/../private"));
+        assert_eq!(config.session.as_deref(), Some("cookie"));
+        assert_eq!(config.rate_limit, Duration::from_secs(60));
+        assert_eq!(config.timeout, Duration::from_secs(5));
+
+        let config = Config::parse(root, settings, |name| match name {
+            "AOC_INPUTS" => Some("/tmp/inputs".into()),
+            "AOC_BASE_URL" => Some("http://localhost:8080".into()),
+            "AOC_RATE_LIMIT" => Some("0".into()),
+            _ => None,
+        })?;
+        assert_eq!(config.inputs, Path::new("/tmp/inputs"));
+        assert_eq!(config.session.as_deref(), Some("cookie"));
+        assert_eq!(config.base_url, "http://localhost:8080");
+        assert_eq!(config.rate_limit, Duration::ZERO);
+
+        assert!(Config::parse(root, "inputs = 3", |_| None).is_err());
+        assert!(Config::parse(root, "rate_limit = -1", |_| None).is_err());
+
+        let timeout = |value: &str| {
+            let value = value.to_owned();
+            Config::parse(root, "timeout = 5", move |name| {
+                (name == "AOC_TIMEOUT").then(|| value.clone().into())
+            })
+        };
+        assert_eq!(timeout("2")?.timeout, Duration::from_secs(2));
+        assert!(timeout("0").is_err());
+        assert!(timeout("2s").is_err());
+        assert!(Config::parse(root, "timeout = 0", |_| None).is_err());
+        Ok(())
+    }
+}
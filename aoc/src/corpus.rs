@@ -0,0 +1,102 @@
+use anyhow::Context;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Directory holding every puzzle input along with their answers, at the root of the workspace.
+pub const DIR: &str = "inputs";
+
+/// Name of the input used when none is given.
+pub const DEFAULT: &str = "default";
+
+/// A named puzzle input, stored as `inputs/day{day}/{name}.txt`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    pub day: u8,
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Input {
+    pub fn read(&self) -> anyhow::Result<String> {
+        fs::read_to_string(&self.path)
+            .with_context(|| format!("Could not read {}", self.path.display()))
+    }
+}
+
+/// Path of the default input of `day`.
+pub fn default_input(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day}")).join(format!("{DEFAULT}.txt"))
+}
+
+/// Lists every input of the corpus, sorted by day then name.
+pub fn inputs(dir: &Path) -> anyhow::Result<Vec<Input>> {
+    let mut inputs = Vec::new();
+
+    for entry in fs::read_dir(dir).with_context(|| format!("Could not read {}", dir.display()))? {
+        let entry = entry?;
+        let Some(day) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_prefix("day"))
+            .and_then(|day| day.parse().ok())
+        else {
+            continue;
+        };
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        for file in fs::read_dir(entry.path())? {
+            let path = file?.path();
+            if path.extension().is_some_and(|extension| extension == "txt") {
+                let name = path
+                    .file_stem()
+                    .context("Input has no file name")?
+                    .to_string_lossy()
+                    .into_owned();
+                inputs.push(Input { day, name, path });
+            }
+        }
+    }
+
+    inputs.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    Ok(inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    #[test]
+    fn test_inputs() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        for path in [
+            "day10/alice.txt",
+            "day2/default.txt",
+            "day2/alice.txt",
+            "day2/notes.md",
+            "template/default.txt",
+        ] {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, "")?;
+        }
+        fs::write(dir.path().join("answers.toml"), "")?;
+
+        let inputs: Vec<_> = super::inputs(dir.path())?
+            .into_iter()
+            .map(|input| (input.day, input.name))
+            .collect();
+        assert_eq!(
+            inputs,
+            [
+                (2, "alice".to_owned()),
+                (2, "default".to_owned()),
+                (10, "alice".to_owned())
+            ]
+        );
+        Ok(())
+    }
+}
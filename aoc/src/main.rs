@@ -0,0 +1,401 @@
+mod answers;
+mod bench;
+mod config;
+mod corpus;
+mod page;
+mod registry;
+mod remote;
+mod report;
+mod scaffold;
+mod source;
+mod submit;
+mod toml_file;
+mod workspace;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use rayon::prelude::*;
+use std::{
+    borrow::Cow,
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Instant,
+};
+use tracing_subscriber::{filter::LevelFilter, EnvFilter};
+
+#[derive(Parser)]
+struct App {
+    /// Log what the solutions are doing to stderr, twice to also log every step
+    #[clap(short, long, action = clap::ArgAction::Count, global = true)]
+    verbose: u8,
+    /// Log filter, such as `day5=trace`, added to the level set by `--verbose`
+    #[clap(long, global = true)]
+    log: Option<String>,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Run one or every registered day
+    Run(RunOpts),
+    /// Check answers against the confirmed answers
+    Verify(VerifyOpts),
+    /// Time parsing and each part over many iterations
+    Bench(BenchOpts),
+    /// Download the input of a day, unless it was already downloaded
+    Fetch {
+        /// Day number
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Submit the answer to a part computed from the day's default input
+    Submit {
+        /// Day number
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Part to submit
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+    },
+    /// Add an example of a saved puzzle page to the corpus, along with its answers
+    Examples(ExamplesOpts),
+    /// Create a new day from the template and register it
+    New {
+        /// Day number
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+}
+
+#[derive(Parser)]
+struct RunOpts {
+    /// Day to run, or `all` to run every registered day
+    day: Selection,
+    /// Part to run, every part is run when omitted. Then input files or glob patterns, `-`
+    /// reading the standard input, with one result per file. Gzip compressed inputs are
+    /// decompressed. Defaults to the day's default input
+    #[clap(value_name = "PART|INPUT")]
+    inputs: Vec<String>,
+    /// Print the time spent parsing and solving each part to stderr, when running a single day
+    #[clap(short, long)]
+    time: bool,
+    /// Output format, json and csv give one record per part with timings and failures
+    #[clap(long, value_enum, default_value_t = report::Format::Text)]
+    format: report::Format,
+    /// Print the intermediate data behind each answer, indented below it. Json records include
+    /// it as `explanation`, csv rows leave it out
+    #[clap(short, long)]
+    explain: bool,
+}
+
+#[derive(Parser)]
+struct VerifyOpts {
+    /// Day to verify, or `all` to verify every registered day
+    day: Selection,
+    /// Input file to verify, its answers are named after its file stem. Defaults to every input
+    /// of the corpus
+    #[clap(long)]
+    input: Option<PathBuf>,
+    /// Record the computed answer of this part as confirmed for the given input
+    #[clap(long, requires = "input", value_parser = clap::value_parser!(u8).range(1..=2))]
+    record: Option<u8>,
+}
+
+#[derive(Parser)]
+struct BenchOpts {
+    /// Day to benchmark, or `all` to benchmark every registered day
+    day: Selection,
+    /// Untimed iterations run before measuring
+    #[clap(long, default_value_t = 10)]
+    warmup: usize,
+    /// Timed iterations
+    #[clap(long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+    iterations: u64,
+    /// Results to compare against, defaults to bench.toml at the root of the workspace
+    #[clap(long)]
+    baseline: Option<PathBuf>,
+    /// Save these results as the new baseline
+    #[clap(long)]
+    save: bool,
+    /// Relative slowdown of the median flagged as a regression
+    #[clap(long, default_value_t = 0.1)]
+    threshold: f64,
+}
+
+#[derive(Parser)]
+struct ExamplesOpts {
+    /// Day number
+    #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Saved HTML page of the puzzle
+    page: PathBuf,
+    /// Name of the input in the corpus
+    #[clap(long, default_value = "sample")]
+    name: String,
+    /// Index of the example to save, counting every example of the page
+    #[clap(long, default_value_t = 0)]
+    example: usize,
+}
+
+#[derive(Clone, Copy)]
+enum Selection {
+    All,
+    Day(u8),
+}
+
+impl FromStr for Selection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s == "all" {
+            Ok(Self::All)
+        } else {
+            Ok(Self::Day(
+                s.parse().context("Expected a day number or `all`")?,
+            ))
+        }
+    }
+}
+
+fn run(config: &config::Config, opts: RunOpts) -> Result<()> {
+    // A leading number is the part, `./1` naming a file called 1.
+    let (part, paths) = match opts.inputs.split_first() {
+        Some((part, paths)) if part.bytes().all(|byte| byte.is_ascii_digit()) => match part.parse()
+        {
+            Ok(part @ 1..=2) => (Some(part), paths),
+            _ => bail!("Part {part} does not exist, it must be 1 or 2"),
+        },
+        _ => (None, &opts.inputs[..]),
+    };
+    let days =
+        match opts.day {
+            Selection::All if !paths.is_empty() => {
+                bail!("Input files can only be given when running a single day")
+            }
+            Selection::All => registry::DAYS.iter().collect(),
+            Selection::Day(number) => vec![registry::find(number)
+                .with_context(|| format!("Day {number} is not registered"))?],
+        };
+
+    // Inputs are all read upfront, the standard input can't be shared by the parallel solves.
+    let inputs: Vec<(&registry::Day, String, Result<Cow<str>>)> = if paths.is_empty() {
+        days.into_iter()
+            .map(|day| {
+                let path = corpus::default_input(&config.inputs, day.day);
+                (day, path.display().to_string(), day.input(&config.inputs))
+            })
+            .collect()
+    } else {
+        source::expand(paths)?
+            .into_iter()
+            .map(|path| {
+                let input = source::read(&path).map(Cow::Owned);
+                (days[0], path, input)
+            })
+            .collect()
+    };
+    let labelled = inputs.len() > 1 && matches!(opts.day, Selection::Day(_));
+
+    // Every input is solved concurrently, and so are its parts once they share a single parse.
+    let start = Instant::now();
+    let records: Vec<report::Record> = inputs
+        .into_par_iter()
+        .flat_map_iter(|(day, path, input)| {
+            let parts: Vec<u8> = match part {
+                Some(part) => vec![part],
+                None => (1..=day.puzzle.parts()).collect(),
+            };
+
+            match input {
+                Ok(input) => {
+                    let mut records = report::solve(day, &parts, &path, &input, config.timeout);
+                    if opts.explain {
+                        report::explain(day, &mut records, &input, config.timeout);
+                    }
+                    records
+                }
+                Err(error) => report::failed(day, &parts, &path, "input", &error),
+            }
+        })
+        .collect();
+    let total = start.elapsed();
+
+    match opts.format {
+        report::Format::Text if matches!(opts.day, Selection::All) => {
+            let answers = answers::Answers::load(config.inputs.join(answers::FILE))?;
+            report::print_table(&records, &answers, total)
+        }
+        report::Format::Text => report::print_text(&records, labelled, opts.time),
+        report::Format::Json => report::write_json(io::stdout().lock(), &records)?,
+        report::Format::Csv => report::write_csv(io::stdout().lock(), &records)?,
+    }
+
+    let failures = records
+        .iter()
+        .filter(|record| record.error.is_some())
+        .count();
+    if failures > 0 {
+        bail!("{failures} parts failed");
+    }
+    Ok(())
+}
+
+fn verify(config: &config::Config, VerifyOpts { day, input, record }: VerifyOpts) -> Result<()> {
+    let corpus = &config.inputs;
+    let mut answers = answers::Answers::load(corpus.join(answers::FILE))?;
+
+    let inputs = match (day, input) {
+        (Selection::All, Some(_)) => bail!("An input file can only be verified for a single day"),
+        (Selection::Day(number), Some(path)) => vec![corpus::Input {
+            day: number,
+            name: path
+                .file_stem()
+                .context("Input path has no file name")?
+                .to_string_lossy()
+                .into_owned(),
+            path,
+        }],
+        (Selection::All, None) => corpus::inputs(corpus)?,
+        (Selection::Day(number), None) => corpus::inputs(corpus)?
+            .into_iter()
+            .filter(|input| input.day == number)
+            .collect(),
+    };
+
+    let mut failures = 0;
+    for input in inputs {
+        let day = registry::find(input.day)
+            .with_context(|| format!("Day {} is not registered", input.day))?;
+        let name = &input.name;
+
+        for (part, status) in answers::verify(day, name, &input.read()?, &answers) {
+            println!("Day {} part {part} ({name}): {status}", day.day);
+            match status {
+                answers::Status::Fail { .. } | answers::Status::Error(..) => failures += 1,
+                answers::Status::Unknown(actual) if record == Some(part) => {
+                    answers.record(day.day, name, part, &actual)?;
+                    answers.save()?;
+                    println!("Recorded {actual} in {}", answers.path().display());
+                }
+                answers::Status::Pass | answers::Status::Unknown(..) => {}
+            }
+        }
+    }
+
+    if failures > 0 {
+        bail!("{failures} answers did not match");
+    }
+    Ok(())
+}
+
+fn bench(root: &Path, config: &config::Config, opts: BenchOpts) -> Result<()> {
+    let days =
+        match opts.day {
+            Selection::All => registry::DAYS.iter().collect(),
+            Selection::Day(number) => vec![registry::find(number)
+                .with_context(|| format!("Day {number} is not registered"))?],
+        };
+    let path = match opts.baseline {
+        Some(path) => path,
+        None => root.join(bench::FILE),
+    };
+    let mut baseline = bench::Baseline::load(path)?;
+
+    let mut measurements = Vec::new();
+    for day in days {
+        measurements.extend(
+            bench::measure(
+                day,
+                &day.input(&config.inputs)?,
+                opts.warmup,
+                opts.iterations as usize,
+            )
+            .with_context(|| format!("Day {}", day.day))?,
+        );
+    }
+    let regressions = bench::print_table(&measurements, &baseline, opts.threshold);
+
+    if opts.save {
+        for measurement in &measurements {
+            baseline.record(measurement)?;
+        }
+        baseline.save()?;
+        println!("Saved results to {}", baseline.path().display());
+    }
+    if regressions > 0 {
+        bail!("{regressions} phases regressed");
+    }
+    Ok(())
+}
+
+/// Sends the events of the solutions to stderr, keeping stdout for the answers.
+fn logging(verbose: u8, log: Option<&str>) -> Result<()> {
+    let level = match verbose {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    let filter = EnvFilter::builder()
+        .with_default_directive(level.into())
+        .parse(log.unwrap_or_default())
+        .context("Invalid log filter")?;
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .init();
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let app = App::parse();
+    logging(app.verbose, app.log.as_deref())?;
+    let root = workspace::root()?;
+    let config = config::Config::load(&root)?;
+
+    match app.command {
+        Command::Run(opts) => run(&config, opts)?,
+        Command::Verify(opts) => verify(&config, opts)?,
+        Command::Bench(opts) => bench(&root, &config, opts)?,
+        Command::Fetch { day } => match remote::fetch(&config, day)? {
+            remote::Fetch::Cached(path) => {
+                println!("Day {day} input is already in {}", path.display())
+            }
+            remote::Fetch::Downloaded(path) => {
+                println!("Saved day {day} input to {}", path.display())
+            }
+        },
+        Command::Submit { day, part } => {
+            let day =
+                registry::find(day).with_context(|| format!("Day {day} is not registered"))?;
+            let submission = submit::submit(&config, day, part)?;
+            println!(
+                "Day {} part {part}: {} is {}",
+                day.day, submission.answer, submission.verdict
+            );
+            println!("{}", submission.message);
+            if submission.verdict != submit::Verdict::Right {
+                bail!("{} was not accepted", submission.answer);
+            }
+        }
+        Command::Examples(opts) => {
+            let html = fs::read_to_string(&opts.page)
+                .with_context(|| format!("Could not read {}", opts.page.display()))?;
+            for change in page::extract(&config.inputs, opts.day, &html, &opts.name, opts.example)?
+            {
+                println!("{change}");
+            }
+        }
+        Command::New { day } => {
+            for change in scaffold::new_day(&root, day)? {
+                println!("{change}");
+            }
+        }
+    }
+
+    Ok(())
+}
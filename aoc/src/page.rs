@@ -0,0 +1,207 @@
+use crate::answers::Answers;
+use anyhow::{bail, Context};
+use std::{fs, path::Path};
+
+/// Description of one part of a puzzle, as found in a saved puzzle page.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Part {
+    /// Every `<pre><code>` block, in order.
+    pub examples: Vec<String>,
+    /// Last emphasized code of the description, which is the answer to the examples.
+    pub answer: Option<String>,
+}
+
+/// Reads the description of every part of a saved puzzle page, part 2 only being there once part
+/// 1 is solved.
+pub fn parts(html: &str) -> Vec<Part> {
+    between(html, "<article", "</article>")
+        .into_iter()
+        .map(|(_, article)| {
+            let article = article.split_once('>').map_or(article, |(_, body)| body);
+            let mut answers = between(article, "<code><em>", "</em></code>");
+            answers.extend(between(article, "<em><code>", "</code></em>"));
+
+            Part {
+                examples: between(article, "<pre><code>", "</code></pre>")
+                    .into_iter()
+                    .map(|(_, example)| text(example))
+                    .collect(),
+                answer: answers
+                    .into_iter()
+                    .max_by_key(|(offset, _)| *offset)
+                    .map(|(_, answer)| text(answer)),
+            }
+        })
+        .collect()
+}
+
+/// Every slice of `html` enclosed by `open` and `close`, along with its offset.
+fn between<'h>(html: &'h str, open: &str, close: &str) -> Vec<(usize, &'h str)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = html[offset..]
+        .find(open)
+        .map(|start| offset + start + open.len())
+    {
+        let Some(end) = html[start..].find(close).map(|end| start + end) else {
+            break;
+        };
+        found.push((start, &html[start..end]));
+        offset = end + close.len();
+    }
+    found
+}
+
+/// Text of an HTML fragment, without its markup and with entities decoded. Whitespace is kept
+/// as is since it matters in examples.
+pub fn text(html: &str) -> String {
+    let mut stripped = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => stripped.push(c),
+            _ => {}
+        }
+    }
+
+    stripped
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Saves an example of a puzzle page to the corpus as the `name` input of `day`, along with the
+/// answers the description gives for it. `example` indexes every example of the page, in order.
+/// Returns a description of every change made.
+pub fn extract(
+    inputs: &Path,
+    day: u8,
+    html: &str,
+    name: &str,
+    example: usize,
+) -> anyhow::Result<Vec<String>> {
+    let parts = parts(html);
+    if parts.is_empty() {
+        bail!("No puzzle description found in the page");
+    }
+    let examples: Vec<&String> = parts.iter().flat_map(|part| &part.examples).collect();
+    let input = examples.get(example).with_context(|| {
+        format!(
+            "Example {example} does not exist, the page has {} examples",
+            examples.len()
+        )
+    })?;
+
+    let mut changes = Vec::new();
+    let path = inputs.join(format!("day{day}")).join(format!("{name}.txt"));
+    fs::create_dir_all(path.parent().context("Input has no parent directory")?)?;
+    fs::write(&path, input).with_context(|| format!("Could not write {}", path.display()))?;
+    changes.push(format!("Saved example {example} to {}", path.display()));
+
+    // Only the parts describing the saved example give its answers. A part without examples of
+    // its own, usually part 2, is about the examples of the part before it.
+    let mut answers = Answers::load(inputs.join(crate::answers::FILE))?;
+    let mut first = 0;
+    let mut described = 0..0;
+    for (part, description) in (1..).zip(&parts) {
+        if !description.examples.is_empty() {
+            described = first..first + description.examples.len();
+            first = described.end;
+        }
+        if !described.contains(&example) {
+            changes.push(format!("Part {part} is about another example"));
+            continue;
+        }
+        match &description.answer {
+            Some(answer) => {
+                answers.record(day, name, part, answer)?;
+                changes.push(format!("Recorded {answer} as the answer to part {part}"));
+            }
+            None => changes.push(format!("No answer found for part {part}")),
+        }
+    }
+    answers.save()?;
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Part;
+    use crate::answers::Answers;
+    use std::fs;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 5: Supply Stacks ---</h2>
+<p>For example:</p>
+<pre><code>    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+</code></pre>
+<p>Stacks look like <code>[A] &amp; [B]</code>, the top crate is <code><em>C</em></code>.</p>
+<p>The top crates are <code><em>CMZ</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>RTGWZTHLD</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>a &lt;- <em>b</em></code></pre>
+<p>In this example, the CrateMover 9001 puts the crates in a totally different order: <em><code>MCD</code></em>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(
+            super::parts(PAGE),
+            [
+                Part {
+                    examples: vec![
+                        "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n\nmove 1 from 2 to 1\n"
+                            .to_owned()
+                    ],
+                    answer: Some("CMZ".to_owned()),
+                },
+                Part {
+                    examples: vec!["a <- b".to_owned()],
+                    answer: Some("MCD".to_owned()),
+                }
+            ]
+        );
+        assert!(super::parts("<p>Not a puzzle</p>").is_empty());
+    }
+
+    #[test]
+    fn test_extract() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let changes = super::extract(dir.path(), 5, PAGE, "sample", 0)?;
+        assert_eq!(changes.len(), 3);
+        assert!(fs::read_to_string(dir.path().join("day5/sample.txt"))?.starts_with("    [D]"));
+
+        let answers = Answers::load(dir.path().join(crate::answers::FILE))?;
+        assert_eq!(answers.get(5, "sample", 1), Some("CMZ"));
+        assert_eq!(answers.get(5, "sample", 2), None);
+
+        let changes = super::extract(dir.path(), 5, PAGE, "arrow", 1)?;
+        assert_eq!(changes[1], "Part 1 is about another example");
+        let answers = Answers::load(dir.path().join(crate::answers::FILE))?;
+        assert_eq!(answers.get(5, "arrow", 1), None);
+        assert_eq!(answers.get(5, "arrow", 2), Some("MCD"));
+
+        // Part 2 reuses the example of part 1
+        let page = PAGE.replace("<pre><code>a &lt;- <em>b</em></code></pre>", "");
+        super::extract(dir.path(), 5, &page, "reused", 0)?;
+        let answers = Answers::load(dir.path().join(crate::answers::FILE))?;
+        assert_eq!(answers.get(5, "reused", 1), Some("CMZ"));
+        assert_eq!(answers.get(5, "reused", 2), Some("MCD"));
+
+        assert!(super::extract(dir.path(), 5, PAGE, "sample", 2).is_err());
+        Ok(())
+    }
+}
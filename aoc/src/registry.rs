@@ -0,0 +1,548 @@
+use common::{Answer as _, Explanation, Solution};
+use std::{
+    any::Any,
+    borrow::Cow,
+    cell::Cell,
+    fmt::{self, Display},
+    hint::black_box,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::{mpsc, Once},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Answer to a single part along with the time spent solving it.
+pub struct Answer {
+    pub part: u8,
+    pub value: anyhow::Result<String>,
+    pub elapsed: Duration,
+}
+
+/// Step of a [`Puzzle::solve_each`], reported as soon as it is done.
+pub enum Progress {
+    /// Time spent parsing the input, or why it could not be.
+    Parsed(anyhow::Result<Duration>),
+    Answered(Answer),
+}
+
+/// Answers of a single run along with the time spent parsing.
+pub struct Run {
+    pub parse: Duration,
+    pub answers: Vec<Answer>,
+}
+
+/// Timings of every repetition of each phase.
+pub struct Samples {
+    pub parse: Vec<Duration>,
+    pub parts: Vec<(u8, Vec<Duration>)>,
+}
+
+/// Object safe view of a [`Solution`], rendering answers to strings so every day can be stored in
+/// the same registry regardless of its input and answer types.
+pub trait Puzzle: Sync {
+    /// Number of parts of the puzzle, either 1 or 2.
+    fn parts(&self) -> u8;
+
+    /// Parses the [normalized](common::normalize) input once and solves the requested parts
+    /// concurrently, each on its own thread, sending every step to `progress` once done. A parse
+    /// failure ends the run, otherwise each part reports its own failures. Panics are caught and
+    /// reported as [`Panic`] errors.
+    fn solve_each(&self, input: &str, parts: &[u8], progress: &mpsc::Sender<Progress>);
+
+    /// [`Puzzle::solve_each`] waiting for every part, answers in the order of `parts`. Only a
+    /// parse failure fails the whole run.
+    fn solve(&self, input: &str, parts: &[u8]) -> anyhow::Result<Run> {
+        let (sender, receiver) = mpsc::channel();
+        self.solve_each(input, parts, &sender);
+        drop(sender);
+        collect(&receiver, parts, None)
+    }
+
+    /// Times `runs` repetitions of parsing and of each part, after `warmup` untimed ones. The
+    /// parts all reuse a single parsed input.
+    fn bench(&self, input: &str, warmup: usize, runs: usize) -> anyhow::Result<Samples>;
+
+    /// Parses the input and describes the intermediate data leading to the answer of `part`.
+    /// Panics are caught and reported as [`Panic`] errors.
+    fn explain(&self, input: &str, part: u8) -> anyhow::Result<Explanation>;
+}
+
+fn sample<T>(
+    warmup: usize,
+    runs: usize,
+    mut phase: impl FnMut() -> anyhow::Result<T>,
+) -> anyhow::Result<Vec<Duration>> {
+    for _ in 0..warmup {
+        black_box(phase()?);
+    }
+    (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            let output = phase()?;
+            let elapsed = start.elapsed();
+            black_box(output);
+            Ok(elapsed)
+        })
+        .collect()
+}
+
+/// A panic caught while parsing or solving, along with its message.
+#[derive(Debug)]
+pub struct Panic(pub String);
+
+impl Panic {
+    fn new(payload: Box<dyn Any + Send>) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown cause".to_owned());
+        Self(message)
+    }
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "panicked: {}", self.0)
+    }
+}
+
+impl std::error::Error for Panic {}
+
+/// A solve that did not finish in time. It keeps running in the background since threads can't
+/// be cancelled, but its result is discarded.
+#[derive(Debug)]
+pub struct Timeout(pub Duration);
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {:?}", self.0)
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+thread_local! {
+    /// Whether the current thread runs a phase whose panics are reported as [`Panic`] errors.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+static QUIET_HOOK: Once = Once::new();
+
+/// Runs a single phase, turning a panic into a [`Panic`] error. The panic is only logged rather
+/// than printed by the default hook, since the error already reports it.
+fn catch<T>(phase: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+    QUIET_HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                tracing::debug!("caught {info}");
+            } else {
+                default(info);
+            }
+        }));
+    });
+
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(phase));
+    CATCHING.set(catching);
+    result.unwrap_or_else(|payload| Err(Panic::new(payload).into()))
+}
+
+/// Calls [`Puzzle::solve_each`] on its own thread, giving up on parsing and then on each part with
+/// a [`Timeout`] error once `timeout` has passed. Parts finishing in time keep their answers even
+/// when another one runs away.
+pub fn solve_within(
+    puzzle: &'static dyn Puzzle,
+    input: &str,
+    parts: &[u8],
+    timeout: Duration,
+) -> anyhow::Result<Run> {
+    let (sender, receiver) = mpsc::channel();
+    let (input, owned) = (input.to_owned(), parts.to_vec());
+    let span = tracing::Span::current();
+    thread::Builder::new().spawn(move || {
+        let _span = span.entered();
+        puzzle.solve_each(&input, &owned, &sender);
+    })?;
+    collect(&receiver, parts, Some(timeout))
+}
+
+/// Gathers the steps of a [`Puzzle::solve_each`] into a run, waiting at most `timeout` for the
+/// parse and then for the parts, which all start once it is done.
+fn collect(
+    receiver: &mpsc::Receiver<Progress>,
+    parts: &[u8],
+    timeout: Option<Duration>,
+) -> anyhow::Result<Run> {
+    let deadline = || timeout.map(|timeout| Instant::now() + timeout);
+    let lost = |error| -> anyhow::Error {
+        match error {
+            mpsc::RecvTimeoutError::Timeout => Timeout(timeout.unwrap_or_default()).into(),
+            mpsc::RecvTimeoutError::Disconnected => {
+                Panic("the solving thread exited early".to_owned()).into()
+            }
+        }
+    };
+    let next = |deadline: Option<Instant>| match deadline {
+        Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+        None => receiver.recv().map_err(mpsc::RecvTimeoutError::from),
+    };
+
+    let parse = match next(deadline()).map_err(lost)? {
+        Progress::Parsed(parse) => parse?,
+        Progress::Answered(_) => anyhow::bail!("A part was answered before parsing"),
+    };
+
+    let deadline = deadline();
+    let mut answers: Vec<Option<Answer>> = parts.iter().map(|_| None).collect();
+    while answers.iter().any(Option::is_none) {
+        match next(deadline) {
+            Ok(Progress::Answered(answer)) => {
+                let slot = answers
+                    .iter_mut()
+                    .zip(parts)
+                    .find(|(slot, &part)| slot.is_none() && part == answer.part);
+                if let Some((slot, _)) = slot {
+                    *slot = Some(answer);
+                }
+            }
+            Ok(Progress::Parsed(_)) => {}
+            Err(error) => {
+                for (slot, &part) in answers.iter_mut().zip(parts) {
+                    slot.get_or_insert_with(|| Answer {
+                        part,
+                        value: Err(lost(error)),
+                        elapsed: timeout.unwrap_or_default(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(Run {
+        parse,
+        answers: answers.into_iter().flatten().collect(),
+    })
+}
+
+/// Calls [`Puzzle::explain`] on its own thread, giving up with a [`Timeout`] error once `timeout`
+/// has passed.
+pub fn explain_within(
+    puzzle: &'static dyn Puzzle,
+    input: &str,
+    part: u8,
+    timeout: Duration,
+) -> anyhow::Result<Explanation> {
+    within(input, timeout, move |input| puzzle.explain(input, part))
+}
+
+fn within<T: Send + 'static>(
+    input: &str,
+    timeout: Duration,
+    phase: impl FnOnce(&str) -> anyhow::Result<T> + Send + 'static,
+) -> anyhow::Result<T> {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_owned();
+    let span = tracing::Span::current();
+    thread::Builder::new().spawn(move || {
+        let _span = span.entered();
+        // The receiver is gone once the phase timed out.
+        let _ = sender.send(phase(&input));
+    })?;
+
+    match receiver.recv_timeout(timeout) {
+        Ok(run) => run,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(Timeout(timeout).into()),
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Err(Panic("the solving thread exited early".to_owned()).into())
+        }
+    }
+}
+
+struct Erased<S>(PhantomData<fn() -> S>);
+
+impl<S: Solution> Erased<S> {
+    fn answer(&self, input: &S::Input<'_>, part: u8) -> Answer {
+        let start = Instant::now();
+        let value = catch(|| match part {
+            1 => S::part1(input).map(|answer| answer.to_string()),
+            2 if self.parts() == 2 => S::part2(input).map(|answer| answer.to_string()),
+            _ => Err(anyhow::anyhow!("Part {part} does not exist")),
+        });
+        let elapsed = start.elapsed();
+        match &value {
+            Ok(answer) => tracing::debug!(part, ?elapsed, answer, "solved"),
+            Err(error) => tracing::debug!(part, ?elapsed, %error, "failed"),
+        }
+        Answer {
+            part,
+            value,
+            elapsed,
+        }
+    }
+
+    /// Two parts unless `Answer2` is `Infallible`, checked when the day is registered.
+    const PARTS: u8 = {
+        assert!(S::Answer1::EXISTS, "A puzzle has at least one part");
+        if S::Answer2::EXISTS {
+            2
+        } else {
+            1
+        }
+    };
+}
+
+impl<S: Solution> Puzzle for Erased<S> {
+    fn parts(&self) -> u8 {
+        Self::PARTS
+    }
+
+    fn solve_each(&self, input: &str, parts: &[u8], progress: &mpsc::Sender<Progress>) {
+        // Sending fails once the receiver gave up waiting, the run is then of no use to anyone.
+        let input = common::normalize(input);
+        let start = Instant::now();
+        let input = match catch(|| S::parse(&input)) {
+            Ok(input) => input,
+            Err(error) => {
+                let _ = progress.send(Progress::Parsed(Err(error)));
+                return;
+            }
+        };
+        let parse = start.elapsed();
+        tracing::debug!(elapsed = ?parse, "parsed the input");
+        let _ = progress.send(Progress::Parsed(Ok(parse)));
+
+        let span = tracing::Span::current();
+        thread::scope(|scope| {
+            for &part in parts {
+                let (input, progress, span) = (&input, progress.clone(), span.clone());
+                scope.spawn(move || {
+                    let _span = span.entered();
+                    let _ = progress.send(Progress::Answered(self.answer(input, part)));
+                });
+            }
+        });
+    }
+
+    fn bench(&self, input: &str, warmup: usize, runs: usize) -> anyhow::Result<Samples> {
+        let input = common::normalize(input);
+        let parse = sample(warmup, runs, || S::parse(&input))?;
+        let input = S::parse(&input)?;
+
+        let mut parts = vec![(1, sample(warmup, runs, || S::part1(&input))?)];
+        if self.parts() == 2 {
+            parts.push((2, sample(warmup, runs, || S::part2(&input))?));
+        }
+
+        Ok(Samples { parse, parts })
+    }
+
+    fn explain(&self, input: &str, part: u8) -> anyhow::Result<Explanation> {
+        if !(1..=self.parts()).contains(&part) {
+            anyhow::bail!("Part {part} does not exist");
+        }
+        let input = common::normalize(input);
+        catch(|| {
+            let input = S::parse(&input)?;
+            let mut explanation = Explanation::new();
+            S::explain(&input, part, &mut explanation)?;
+            Ok(explanation)
+        })
+    }
+}
+
+/// A registered day. With the `embedded-inputs` feature its `inputs/day{day}/default.txt` input is
+/// embedded in the binary.
+pub struct Day {
+    pub day: u8,
+    #[cfg(feature = "embedded-inputs")]
+    embedded: &'static str,
+    pub puzzle: &'static dyn Puzzle,
+}
+
+impl Day {
+    /// Default input of the day, read from the `inputs` directory unless it is embedded.
+    #[cfg(not(feature = "embedded-inputs"))]
+    pub fn input(&self, inputs: &Path) -> anyhow::Result<Cow<'static, str>> {
+        let path = inputs.join(format!("day{}", self.day)).join("default.txt");
+        match std::fs::read_to_string(&path) {
+            Ok(input) => Ok(Cow::Owned(input)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => anyhow::bail!(
+                "Input missing for day {}, expected it at {}",
+                self.day,
+                path.display()
+            ),
+            Err(error) => {
+                Err(anyhow::Error::new(error).context(format!("Could not read {}", path.display())))
+            }
+        }
+    }
+
+    /// Default input of the day, embedded in the binary.
+    #[cfg(feature = "embedded-inputs")]
+    pub fn input(&self, _inputs: &Path) -> anyhow::Result<Cow<'static, str>> {
+        Ok(Cow::Borrowed(self.embedded))
+    }
+}
+
+macro_rules! day {
+    ($day:literal, $krate:ident :: $solution:ident) => {
+        Day {
+            day: $day,
+            #[cfg(feature = "embedded-inputs")]
+            embedded: include_str!(concat!("../../inputs/", stringify!($krate), "/default.txt")),
+            puzzle: &Erased::<$krate::$solution>(PhantomData),
+        }
+    };
+}
+
+/// Every solved day, in order.
+pub static DAYS: &[Day] = &[
+    day!(1, day1::Day1),
+    day!(2, day2::Day2),
+    day!(3, day3::Day3),
+    day!(4, day4::Day4),
+    day!(5, day5::Day5),
+    day!(6, day6::Day6),
+    day!(7, day7::Day7),
+];
+
+/// Looks up a registered day by number.
+pub fn find(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|entry| entry.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Erased, Panic, Puzzle, Timeout};
+    use common::{Explanation, Solution};
+    use std::{convert::Infallible, marker::PhantomData, thread, time::Duration};
+
+    /// Single part puzzle drawing its input as a box.
+    struct Boxed;
+
+    impl Solution for Boxed {
+        type Input<'i> = &'i str;
+        type Answer1 = String;
+        type Answer2 = Infallible;
+
+        fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+            Ok(input.trim())
+        }
+
+        fn part1(input: &Self::Input<'_>) -> anyhow::Result<Self::Answer1> {
+            let border = "#".repeat(input.len() + 2);
+            Ok(format!("{border}\n#{input}#\n{border}"))
+        }
+    }
+
+    #[test]
+    fn test_single_part() -> anyhow::Result<()> {
+        let puzzle = Erased::<Boxed>(PhantomData);
+        assert_eq!(puzzle.parts(), 1);
+        assert_eq!(SLOW.parts(), 2);
+
+        let run = puzzle.solve("ab\n", &[1, 2])?;
+        assert_eq!(run.answers[0].value.as_ref().unwrap(), "####\n#ab#\n####");
+        assert_eq!(
+            run.answers[1].value.as_ref().unwrap_err().to_string(),
+            "Part 2 does not exist"
+        );
+
+        let samples = puzzle.bench("ab", 0, 3)?;
+        assert_eq!(samples.parts.len(), 1);
+
+        assert!(puzzle.explain("ab", 1)?.is_empty());
+        assert!(puzzle.explain("ab", 2).is_err());
+        Ok(())
+    }
+
+    /// Puzzle whose first part never finishes in time, nor its parse of `slow`.
+    struct Slow;
+
+    impl Solution for Slow {
+        type Input<'i> = ();
+        type Answer1 = u8;
+        type Answer2 = u8;
+
+        fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+            if input == "slow\n" {
+                thread::sleep(Duration::from_secs(5));
+            }
+            Ok(())
+        }
+
+        fn part1(_input: &Self::Input<'_>) -> anyhow::Result<Self::Answer1> {
+            thread::sleep(Duration::from_secs(5));
+            Ok(1)
+        }
+
+        fn part2(_input: &Self::Input<'_>) -> anyhow::Result<Self::Answer2> {
+            Ok(2)
+        }
+
+        fn explain(
+            _input: &Self::Input<'_>,
+            _part: u8,
+            _explanation: &mut Explanation,
+        ) -> anyhow::Result<()> {
+            thread::sleep(Duration::from_secs(5));
+            Ok(())
+        }
+    }
+
+    static SLOW: Erased<Slow> = Erased(PhantomData);
+
+    #[test]
+    fn test_panic() -> anyhow::Result<()> {
+        let day5 = super::find(5).unwrap();
+        let run = day5
+            .puzzle
+            .solve("[A]\n 1 \n\nmove 1 from 0 to 1\n", &[1, 2])?;
+        for answer in run.answers {
+            let error = answer.value.unwrap_err();
+            assert!(error.downcast_ref::<Panic>().is_some(), "{error}");
+            assert!(error.to_string().starts_with("panicked: "));
+        }
+
+        let error = day5
+            .puzzle
+            .explain("[A]\n 1 \n\nmove 1 from 0 to 1\n", 1)
+            .unwrap_err();
+        assert!(error.downcast_ref::<Panic>().is_some(), "{error}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_timeout() -> anyhow::Result<()> {
+        // Only the slow part times out, the other one still answers
+        let run = super::solve_within(&SLOW, "", &[1, 2], Duration::from_millis(50))?;
+        let error = run.answers[0].value.as_ref().unwrap_err();
+        assert!(error.downcast_ref::<Timeout>().is_some());
+        assert_eq!(error.to_string(), "timed out after 50ms");
+        assert_eq!(run.answers[1].value.as_ref().unwrap(), "2");
+
+        let Err(error) = super::solve_within(&SLOW, "slow", &[2], Duration::from_millis(50)) else {
+            panic!("The slow parse finished in time");
+        };
+        assert!(error.downcast_ref::<Timeout>().is_some());
+
+        let error = super::explain_within(&SLOW, "", 2, Duration::from_millis(50)).unwrap_err();
+        assert!(error.downcast_ref::<Timeout>().is_some());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(feature = "embedded-inputs"))]
+    fn test_missing_input() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let error = super::find(3).unwrap().input(dir.path()).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Input missing for day 3, expected it at "));
+        Ok(())
+    }
+}
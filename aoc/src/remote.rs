@@ -0,0 +1,296 @@
+use crate::{config::Config, corpus};
+use anyhow::{bail, Context};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Year of the puzzles.
+pub const YEAR: u16 = 2022;
+
+/// Time of the last request to the website, shared by every run, in the inputs directory.
+const LAST_REQUEST: &str = ".last-request";
+
+const USER_AGENT: &str = concat!(
+    "aoc2022 runner by ",
+    env!("CARGO_PKG_AUTHORS"),
+    " (",
+    env!("CARGO_PKG_NAME"),
+    " ",
+    env!("CARGO_PKG_VERSION"),
+    ")"
+);
+
+/// Client of the puzzle website, authenticated with the session cookie of a logged in user.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    limit: RateLimit,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        let session = config
+            .session
+            .clone()
+            .context("No session token, set `session` in aoc.toml or AOC_SESSION")?;
+
+        Ok(Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: config.base_url.trim_end_matches('/').to_owned(),
+            session,
+            limit: RateLimit {
+                path: config.inputs.join(LAST_REQUEST),
+                interval: config.rate_limit,
+            },
+        })
+    }
+
+    /// Downloads the puzzle input of `day`.
+    pub fn input(&self, day: u8) -> anyhow::Result<String> {
+        self.limit.wait()?;
+
+        let url = format!("{}/{YEAR}/day/{day}/input", self.base_url);
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call();
+        read(day, &url, response)
+    }
+
+    /// Posts an answer to `part` of `day`, returning the page describing the verdict.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> anyhow::Result<String> {
+        self.limit.wait()?;
+
+        let url = format!("{}/{YEAR}/day/{day}/answer", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+        read(day, &url, response)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn read(
+    day: u8,
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> anyhow::Result<String> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .with_context(|| format!("Could not read the response of {url}")),
+        Err(ureq::Error::Status(404, _)) => bail!("Day {day} is not available yet"),
+        Err(ureq::Error::Status(400 | 401 | 403, _)) => {
+            bail!("The session token was rejected, it may have expired")
+        }
+        Err(ureq::Error::Status(status, _)) => bail!("{url} answered with status {status}"),
+        Err(error) => Err(error).with_context(|| format!("Could not reach {url}")),
+    }
+}
+
+/// Spaces requests by at least `interval`, across runs, by remembering the time of the last one.
+struct RateLimit {
+    path: PathBuf,
+    interval: Duration,
+}
+
+impl RateLimit {
+    /// Sleeps until the interval since the last request has passed, then records a new request.
+    fn wait(&self) -> anyhow::Result<()> {
+        let last = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+
+        if let Some(last) = last {
+            let remaining = remaining(last, SystemTime::now(), self.interval);
+            if !remaining.is_zero() {
+                eprintln!("Waiting {remaining:.1?} before the next request");
+                thread::sleep(remaining);
+            }
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        fs::write(&self.path, now.as_millis().to_string())
+            .with_context(|| format!("Could not write {}", self.path.display()))
+    }
+}
+
+/// Time left to wait at `now` so requests are at least `interval` apart. A last request in the
+/// future, after the clock was turned back, waits the whole interval.
+fn remaining(last: SystemTime, now: SystemTime, interval: Duration) -> Duration {
+    interval.saturating_sub(now.duration_since(last).unwrap_or_default())
+}
+
+/// Outcome of [`fetch`], with the path of the input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetch {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Downloads the input of `day` as its default input. An input already present is never
+/// downloaded again, unless it is the empty placeholder created along with the day.
+pub fn fetch(config: &Config, day: u8) -> anyhow::Result<Fetch> {
+    let path = corpus::default_input(&config.inputs, day);
+    if fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(Fetch::Cached(path));
+    }
+
+    let input = Client::new(config)?.input(day)?;
+    save(&path, &input)?;
+    Ok(Fetch::Downloaded(path))
+}
+
+/// Writes through a temporary file so an interrupted download never looks cached.
+fn save(path: &Path, contents: &str) -> anyhow::Result<()> {
+    let staging = path.with_extension("tmp");
+    fs::create_dir_all(path.parent().context("Input has no parent directory")?)?;
+    fs::write(&staging, contents)
+        .with_context(|| format!("Could not write {}", staging.display()))?;
+    fs::rename(&staging, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::Fetch;
+    use crate::config::Config;
+    use std::{
+        fs,
+        path::Path,
+        sync::{Arc, Mutex},
+        thread,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    /// A request received by the [`server`], with its body.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Request {
+        pub method: String,
+        pub url: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    /// Stands in for the website, answering every request with `respond`. Returns its base URL
+    /// and every request received so far.
+    pub fn server(
+        respond: impl Fn(&Request) -> (u16, String) + Send + 'static,
+    ) -> (String, Arc<Mutex<Vec<Request>>>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let received = Arc::clone(&requests);
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let received_request = Request {
+                    method: request.method().to_string(),
+                    url: request.url().to_owned(),
+                    cookie: request
+                        .headers()
+                        .iter()
+                        .find(|header| header.field.equiv("Cookie"))
+                        .map(|header| header.value.to_string()),
+                    body,
+                };
+                let (status, body) = respond(&received_request);
+                received.lock().unwrap().push(received_request);
+                request
+                    .respond(tiny_http::Response::from_string(body).with_status_code(status))
+                    .unwrap();
+            }
+        });
+
+        (format!("http://127.0.0.1:{port}"), requests)
+    }
+
+    pub fn config(inputs: &Path, base_url: String) -> Config {
+        Config {
+            inputs: inputs.to_owned(),
+            session: Some("secret".to_owned()),
+            base_url,
+            rate_limit: Duration::ZERO,
+            timeout: crate::config::TIMEOUT,
+        }
+    }
+
+    #[test]
+    fn test_fetch() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let (base_url, requests) = server(|request| match request.url.as_str() {
+            "/2022/day/3/input" if request.cookie.as_deref() == Some("session=secret") => {
+                (200, "vJrwpWtwJgWrhcsFMMfFFhFp\n".to_owned())
+            }
+            "/2022/day/3/input" => (400, "Please log in".to_owned()),
+            "/2022/day/5/input" => (500, "Internal server error".to_owned()),
+            _ => (404, "Not found".to_owned()),
+        });
+        let mut config = config(dir.path(), base_url);
+
+        let path = dir.path().join("day3").join("default.txt");
+        fs::create_dir_all(dir.path().join("day3"))?;
+        fs::write(&path, "")?;
+        assert_eq!(super::fetch(&config, 3)?, Fetch::Downloaded(path.clone()));
+        assert_eq!(fs::read_to_string(&path)?, "vJrwpWtwJgWrhcsFMMfFFhFp\n");
+        assert_eq!(super::fetch(&config, 3)?, Fetch::Cached(path));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        let error = super::fetch(&config, 4).unwrap_err();
+        assert_eq!(error.to_string(), "Day 4 is not available yet");
+        assert!(!dir.path().join("day4").join("default.txt").exists());
+
+        fs::remove_dir_all(dir.path().join("day3"))?;
+        config.session = Some("expired".to_owned());
+        let error = super::fetch(&config, 3).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The session token was rejected, it may have expired"
+        );
+
+        config.session = None;
+        assert!(super::fetch(&config, 3).is_err());
+        assert_eq!(requests.lock().unwrap().len(), 3);
+
+        config.session = Some("secret".to_owned());
+        let error = super::fetch(&config, 5).unwrap_err();
+        assert!(error
+            .to_string()
+            .ends_with("/2022/day/5/input answered with status 500"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let last = UNIX_EPOCH + Duration::from_secs(100);
+        let interval = Duration::from_secs(10);
+
+        assert_eq!(
+            super::remaining(last, last + Duration::from_secs(4), interval),
+            Duration::from_secs(6)
+        );
+        assert_eq!(
+            super::remaining(last, last + Duration::from_secs(30), interval),
+            Duration::ZERO
+        );
+        assert_eq!(
+            super::remaining(last, last - Duration::from_secs(5), interval),
+            interval
+        );
+    }
+}
@@ -0,0 +1,492 @@
+use crate::{
+    answers::Answers,
+    registry::{self, Day, Panic, Timeout},
+};
+use serde::Serialize;
+use std::{
+    fmt::{self, Display},
+    io::Write,
+    path::Path,
+    time::Duration,
+};
+
+/// Output format of the runner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Answers only, one per line
+    Text,
+    /// A single array of records
+    Json,
+    /// One record per row, after a header row
+    Csv,
+}
+
+/// Result of solving one part of a day on one input.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    /// Path of the input, as given or where the default input lives.
+    pub input: String,
+    pub answer: Option<String>,
+    /// Seconds spent parsing the input, shared by every part of a run.
+    pub parse_time: Option<f64>,
+    /// Seconds spent solving the part.
+    pub solve_time: Option<f64>,
+    pub error: Option<Failure>,
+    /// Intermediate data behind the answer, only when asked for. Left out of CSV rows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+}
+
+/// Why a part has no answer.
+#[derive(Debug, Serialize)]
+pub struct Failure {
+    /// Phase that failed: `input`, `parse` or `solve`, or `panic` and `timeout` whatever the
+    /// phase.
+    pub phase: &'static str,
+    pub message: String,
+    /// Underlying causes, outermost first.
+    pub causes: Vec<String>,
+}
+
+impl Failure {
+    pub fn new(phase: &'static str, error: &anyhow::Error) -> Self {
+        let phase = if error.is::<Panic>() {
+            "panic"
+        } else if error.is::<Timeout>() {
+            "timeout"
+        } else {
+            phase
+        };
+        Self {
+            phase,
+            message: error.to_string(),
+            causes: error.chain().skip(1).map(ToString::to_string).collect(),
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        for cause in &self.causes {
+            write!(f, ": {cause}")?;
+        }
+        Ok(())
+    }
+}
+
+fn record(day: &Day, part: u8, path: &str) -> Record {
+    Record {
+        day: day.day,
+        part,
+        input: path.to_owned(),
+        answer: None,
+        parse_time: None,
+        solve_time: None,
+        error: None,
+        explanation: None,
+    }
+}
+
+/// Records every part of `parts` as failed in the same `phase`.
+pub fn failed(
+    day: &Day,
+    parts: &[u8],
+    path: &str,
+    phase: &'static str,
+    error: &anyhow::Error,
+) -> Vec<Record> {
+    parts
+        .iter()
+        .map(|&part| Record {
+            error: Some(Failure::new(phase, error)),
+            ..record(day, part, path)
+        })
+        .collect()
+}
+
+/// Solves `parts` of `day` concurrently on the `input` found at `path`, one record per part. A
+/// failure to parse the input, or parsing for longer than `timeout`, fails every part, while a
+/// part solving for longer than `timeout` only fails itself.
+pub fn solve(day: &Day, parts: &[u8], path: &str, input: &str, timeout: Duration) -> Vec<Record> {
+    let _span = tracing::info_span!("solve", day = day.day, input = path).entered();
+    let run = match registry::solve_within(day.puzzle, input, parts, timeout) {
+        Ok(run) => run,
+        // Only the parse can fail the whole run, a timeout being labelled as such by `Failure`
+        Err(error) => return failed(day, parts, path, "parse", &error),
+    };
+
+    run.answers
+        .into_iter()
+        .map(|answer| {
+            let (value, error) = match answer.value {
+                Ok(value) => (Some(value), None),
+                Err(error) => (None, Some(Failure::new("solve", &error))),
+            };
+            Record {
+                answer: value,
+                parse_time: Some(run.parse.as_secs_f64()),
+                solve_time: Some(answer.elapsed.as_secs_f64()),
+                error,
+                ..record(day, answer.part, path)
+            }
+        })
+        .collect()
+}
+
+/// Attaches to every answered record the explanation of its part, on the same `input`. A day
+/// failing to explain itself, or taking longer than `timeout` to, is only logged.
+pub fn explain(day: &Day, records: &mut [Record], input: &str, timeout: Duration) {
+    for record in records.iter_mut().filter(|record| record.answer.is_some()) {
+        match registry::explain_within(day.puzzle, input, record.part, timeout) {
+            Ok(explanation) if explanation.is_empty() => {}
+            Ok(explanation) => record.explanation = Some(explanation.to_string()),
+            Err(error) => tracing::warn!(
+                day = day.day,
+                part = record.part,
+                "could not explain the answer: {error:#}"
+            ),
+        }
+    }
+}
+
+/// Indents every line of an explanation under the answer it belongs to.
+fn indented(explanation: &str) -> String {
+    explanation
+        .lines()
+        .map(|line| format!("  {line}\n"))
+        .collect()
+}
+
+/// Prints answers to stdout, labelled with their day, part and input when `labelled`, and
+/// failures and timings to stderr.
+pub fn print_text(records: &[Record], labelled: bool, time: bool) {
+    let mut previous = None;
+    for record in records {
+        let Record { day, part, .. } = record;
+        let (label, run_label) = if labelled {
+            (
+                format!("Day {day} part {part} ({})", record.input),
+                format!("Day {day} ({})", record.input),
+            )
+        } else {
+            (format!("Day {day} part {part}"), format!("Day {day}"))
+        };
+
+        // Parsing is shared by the parts of a run, only report it once.
+        let run = Some((day, &record.input));
+        if let (true, true, Some(parse)) = (time, previous != run, record.parse_time) {
+            eprintln!(
+                "{run_label} parse took {:?}",
+                Duration::from_secs_f64(parse)
+            );
+        }
+        previous = run;
+
+        match (&record.answer, &record.error) {
+            (Some(answer), _) if labelled && answer.contains('\n') => {
+                println!("{label}:\n{answer}")
+            }
+            (Some(answer), _) if labelled => println!("{label}: {answer}"),
+            (Some(answer), _) => println!("{answer}"),
+            (None, Some(error)) => eprintln!("{label} failed: {error}"),
+            (None, None) => {}
+        }
+        if let Some(explanation) = &record.explanation {
+            print!("{}", indented(explanation));
+        }
+        if let (true, Some(solve)) = (time, record.solve_time) {
+            eprintln!("{label} took {:?}", Duration::from_secs_f64(solve));
+        }
+    }
+}
+
+/// Prints the [`table`] of every record to stdout followed by their explanations, and their
+/// failures to stderr.
+pub fn print_table(records: &[Record], answers: &Answers, total: Duration) {
+    print!("{}", table(records, answers, total));
+    for record in records {
+        if let Some(explanation) = &record.explanation {
+            println!("\nDay {} part {}:", record.day, record.part);
+            print!("{}", indented(explanation));
+        }
+    }
+    for record in records {
+        if let Some(error) = &record.error {
+            eprintln!("Day {} part {} failed: {error}", record.day, record.part);
+        }
+    }
+}
+
+/// Renders every record as a row of an aligned table, checking answers against the confirmed
+/// ones, followed by the `total` wall clock time. The parse time shared by the parts of a run is
+/// only shown on its first row.
+pub fn table(records: &[Record], answers: &Answers, total: Duration) -> String {
+    let mut previous = None;
+    let rows: Vec<[String; 6]> = records
+        .iter()
+        .map(|record| {
+            let name = Path::new(&record.input)
+                .file_stem()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default();
+            let expected = answers.get(record.day, &name, record.part);
+            let (answer, check) = match (&record.answer, &record.error) {
+                (Some(answer), _) => {
+                    let check = match expected {
+                        Some(expected) if expected == answer => "✓",
+                        Some(_) => "✗",
+                        None => "?",
+                    };
+                    let mut lines = answer.lines();
+                    let first = lines.next().unwrap_or_default().to_owned();
+                    (
+                        if lines.next().is_some() {
+                            first + " …"
+                        } else {
+                            first
+                        },
+                        check,
+                    )
+                }
+                (None, Some(error)) => (format!("{} failed", error.phase), "✗"),
+                (None, None) => (String::new(), "✗"),
+            };
+            let time = |seconds: Option<f64>| {
+                seconds
+                    .map(|seconds| format!("{:.2?}", Duration::from_secs_f64(seconds)))
+                    .unwrap_or_default()
+            };
+            let run = Some((record.day, &record.input));
+            let parse = if previous == run {
+                String::new()
+            } else {
+                time(record.parse_time)
+            };
+            previous = run;
+
+            [
+                record.day.to_string(),
+                record.part.to_string(),
+                answer,
+                parse,
+                time(record.solve_time),
+                check.to_owned(),
+            ]
+        })
+        .collect();
+
+    let header = ["Day", "Part", "Answer", "Parse", "Solve", "Check"].map(str::to_owned);
+    let mut widths = [0; 6];
+    for row in rows.iter().chain([&header]) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    for row in [&header].into_iter().chain(&rows) {
+        let [day, part, answer, parse, solve, check] = row;
+        table += &format!(
+            "{day:>w0$}  {part:>w1$}  {answer:<w2$}  {parse:>w3$}  {solve:>w4$}  {check}\n",
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+        );
+    }
+    table += &format!("Total runtime {total:.2?} for {} parts\n", records.len());
+    table
+}
+
+/// Writes every record as a single array, ending the output with a newline.
+pub fn write_json(mut out: impl Write, records: &[Record]) -> anyhow::Result<()> {
+    serde_json::to_writer_pretty(&mut out, records)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Writes one row per record, the error being flattened into its phase and full message.
+pub fn write_csv(out: impl Write, records: &[Record]) -> anyhow::Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record([
+        "day",
+        "part",
+        "input",
+        "answer",
+        "parse_time",
+        "solve_time",
+        "error_phase",
+        "error",
+    ])?;
+
+    let optional = |value: Option<f64>| value.map(|value| value.to_string()).unwrap_or_default();
+    for record in records {
+        writer.write_record([
+            record.day.to_string(),
+            record.part.to_string(),
+            record.input.clone(),
+            record.answer.clone().unwrap_or_default(),
+            optional(record.parse_time),
+            optional(record.solve_time),
+            record
+                .error
+                .as_ref()
+                .map(|error| error.phase.to_owned())
+                .unwrap_or_default(),
+            record
+                .error
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{answers::Answers, registry};
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn test_records() -> anyhow::Result<()> {
+        let day = registry::find(6).unwrap();
+        let records = super::solve(
+            day,
+            &[1, 2],
+            "sample.txt",
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            TIMEOUT,
+        );
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].answer.as_deref(), Some("7"));
+        assert_eq!(records[1].answer.as_deref(), Some("19"));
+        assert!(records.iter().all(|record| record.error.is_none()));
+
+        let missing = anyhow::anyhow!("No such file").context("Could not read a.txt");
+        let records = super::failed(day, &[1, 2], "a.txt", "input", &missing);
+        let error = records[1].error.as_ref().unwrap();
+        assert_eq!(error.phase, "input");
+        assert_eq!(error.to_string(), "Could not read a.txt: No such file");
+
+        let day = registry::find(1).unwrap();
+        let records = super::solve(day, &[2], "bad.txt", "12\nabc\n", TIMEOUT);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].error.as_ref().unwrap().phase, "parse");
+
+        let day = registry::find(5).unwrap();
+        let records = super::solve(
+            day,
+            &[1],
+            "bad.txt",
+            "[A]\n 1 \n\nmove 1 from 0 to 1\n",
+            TIMEOUT,
+        );
+        let error = records[0].error.as_ref().unwrap();
+        assert_eq!(error.phase, "panic");
+        assert!(error.message.starts_with("panicked: "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain() {
+        let day = registry::find(6).unwrap();
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        let mut records = super::solve(day, &[1, 2], "sample.txt", input, TIMEOUT);
+        records.extend(super::solve(day, &[1], "bad.txt", "ab", TIMEOUT));
+        super::explain(day, &mut records, input, TIMEOUT);
+
+        assert_eq!(
+            records[0].explanation.as_deref(),
+            Some("Characters 4 to 7 are 4 different ones: jpqm\n")
+        );
+        assert_eq!(
+            super::indented(records[1].explanation.as_deref().unwrap()),
+            "  Characters 6 to 19 are 14 different ones: qmgbljsphdztnv\n"
+        );
+        assert_eq!(records[2].explanation, None);
+    }
+
+    #[test]
+    fn test_table() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut answers = Answers::load(dir.path().join("answers.toml"))?;
+        answers.record(6, "sample", 1, "7")?;
+        answers.record(6, "sample", 2, "20")?;
+
+        let day = registry::find(6).unwrap();
+        let mut records = super::solve(
+            day,
+            &[1, 2],
+            "day6/sample.txt",
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            TIMEOUT,
+        );
+        records.extend(super::solve(day, &[1], "day6/other.txt", "ab", TIMEOUT));
+        for record in &mut records {
+            record.parse_time = Some(0.001);
+            record.solve_time = Some(0.0005);
+        }
+
+        assert_eq!(
+            super::table(&records, &answers, Duration::from_millis(3)),
+            "\
+Day  Part  Answer         Parse     Solve  Check
+  6     1  7             1.00ms  500.00µs  ✓
+  6     2  19                    500.00µs  ✗
+  6     1  solve failed  1.00ms  500.00µs  ✗
+Total runtime 3.00ms for 3 parts
+"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_formats() -> anyhow::Result<()> {
+        let day = registry::find(6).unwrap();
+        let mut records = super::solve(
+            day,
+            &[1],
+            "sample.txt",
+            "bvwbjplbgvbhsrlpgdmjqwftvncz",
+            TIMEOUT,
+        );
+        records.extend(super::solve(day, &[2], "bad.txt", "ab", TIMEOUT));
+        for record in &mut records {
+            record.parse_time = Some(0.5);
+            record.solve_time = record.solve_time.map(|_| 0.25);
+        }
+
+        let mut json = Vec::new();
+        super::write_json(&mut json, &records)?;
+        assert!(json.ends_with(b"]\n"));
+        let json: serde_json::Value = serde_json::from_slice(&json)?;
+        assert_eq!(json[0]["answer"], "5");
+        assert_eq!(json[0]["error"], serde_json::Value::Null);
+        assert_eq!(json[1]["input"], "bad.txt");
+        assert_eq!(json[1]["answer"], serde_json::Value::Null);
+        assert_eq!(json[1]["error"]["phase"], "solve");
+
+        let mut csv = Vec::new();
+        super::write_csv(&mut csv, &records)?;
+        let csv = String::from_utf8(csv)?;
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("day,part,input,answer,parse_time,solve_time,error_phase,error")
+        );
+        assert_eq!(lines.next(), Some("6,1,sample.txt,5,0.5,0.25,,"));
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("6,2,bad.txt,,0.5,0.25,solve,"));
+        Ok(())
+    }
+}
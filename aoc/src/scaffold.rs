@@ -0,0 +1,282 @@
+use crate::corpus;
+use anyhow::{bail, Context};
+use std::{fs, path::Path};
+use toml_edit::{Document, Value};
+
+/// Creates `day{day}` from the template and registers it with the workspace and the runner.
+///
+/// An existing day is never overwritten and every registration step is skipped when already done,
+/// so rerunning the command is harmless and completes a partially scaffolded day. Returns a
+/// description of every change made.
+pub fn new_day(root: &Path, day: u8) -> anyhow::Result<Vec<String>> {
+    let name = format!("day{day}");
+    let mut changes = Vec::new();
+
+    if root.join(&name).exists() {
+        changes.push(format!("{name} already exists, left untouched"));
+    } else {
+        copy_template(root, &name, day)?;
+        changes.push(format!("Created {name} from the template"));
+    }
+    if add_input(root, day)? {
+        changes.push(format!(
+            "Created an empty default input, paste the puzzle input in {}",
+            corpus::default_input(Path::new(corpus::DIR), day).display()
+        ));
+    }
+    if add_member(root, &name, day)? {
+        changes.push(format!("Added {name} to the workspace members"));
+    }
+    if add_dependency(root, &name)? {
+        changes.push(format!("Added {name} to the runner dependencies"));
+    }
+    if register(root, &name, day)? {
+        changes.push(format!("Registered {name} with the runner"));
+    }
+
+    Ok(changes)
+}
+
+fn day_number(name: &str) -> Option<u8> {
+    name.strip_prefix("day")?.parse().ok()
+}
+
+/// Copies the template into a staging directory first so a failure never leaves a half created
+/// day behind.
+fn copy_template(root: &Path, name: &str, day: u8) -> anyhow::Result<()> {
+    let staging = root.join(format!(".{name}.tmp"));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    copy_dir(&root.join("template"), &staging, name, day)
+        .with_context(|| format!("Could not copy the template to {}", staging.display()))?;
+    fs::rename(&staging, root.join(name))?;
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path, name: &str, day: u8) -> anyhow::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != "target" {
+                copy_dir(&path, &target, name, day)?;
+            }
+        } else if entry.file_name() == "Cargo.toml" {
+            let mut manifest: Document = fs::read_to_string(&path)?.parse()?;
+            let package = manifest["package"]["name"]
+                .as_value_mut()
+                .context("Template package has no name")?;
+            let decor = package.decor().clone();
+            *package = name.into();
+            *package.decor_mut() = decor;
+            fs::write(target, manifest.to_string())?;
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            let source = fs::read_to_string(&path)?;
+            fs::write(target, source.replace("Template", &format!("Day{day}")))?;
+        } else {
+            fs::copy(&path, target)?;
+        }
+    }
+    Ok(())
+}
+
+/// With the `embedded-inputs` feature the registry embeds the default input, so it has to exist
+/// even if empty.
+fn add_input(root: &Path, day: u8) -> anyhow::Result<bool> {
+    let path = corpus::default_input(&root.join(corpus::DIR), day);
+    if path.exists() {
+        return Ok(false);
+    }
+    fs::create_dir_all(path.parent().context("Input has no parent directory")?)?;
+    fs::write(path, "")?;
+    Ok(true)
+}
+
+fn add_member(root: &Path, name: &str, day: u8) -> anyhow::Result<bool> {
+    let path = root.join("Cargo.toml");
+    let mut manifest: Document = fs::read_to_string(&path)?.parse()?;
+    let members = manifest["workspace"]["members"]
+        .as_array_mut()
+        .context("Workspace members are not an array")?;
+
+    if members.iter().any(|member| member.as_str() == Some(name)) {
+        return Ok(false);
+    }
+
+    // Keep the days sorted, ahead of the other members.
+    let days: Vec<(usize, u8)> = members
+        .iter()
+        .enumerate()
+        .filter_map(|(index, member)| Some((index, day_number(member.as_str()?)?)))
+        .collect();
+    let index = match days.iter().rev().find(|(_, number)| *number < day) {
+        Some((index, _)) => index + 1,
+        None => days.first().map_or(0, |(index, _)| *index),
+    };
+
+    let mut member = Value::from(name);
+    member.decor_mut().set_prefix("\n    ");
+    members.insert_formatted(index, member);
+    fs::write(path, manifest.to_string())?;
+    Ok(true)
+}
+
+fn add_dependency(root: &Path, name: &str) -> anyhow::Result<bool> {
+    let path = root.join("aoc").join("Cargo.toml");
+    let mut manifest: Document = fs::read_to_string(&path)?.parse()?;
+    let dependencies = manifest["dependencies"]
+        .as_table_mut()
+        .context("Runner dependencies are not a table")?;
+
+    if dependencies.contains_key(name) {
+        return Ok(false);
+    }
+
+    let mut dependency = toml_edit::InlineTable::new();
+    dependency.insert("path", format!("../{name}").into());
+    dependencies.insert(name, toml_edit::value(dependency));
+    fs::write(path, manifest.to_string())?;
+    Ok(true)
+}
+
+/// Adds the day to the `DAYS` list of the runner's registry, keeping it sorted.
+fn register(root: &Path, name: &str, day: u8) -> anyhow::Result<bool> {
+    let path = root.join("aoc").join("src").join("registry.rs");
+    let source = fs::read_to_string(&path)?;
+
+    let Some(start) = source.find("pub static DAYS") else {
+        bail!("Could not find the list of days in {}", path.display());
+    };
+    let Some(end) = source[start..].find("\n];").map(|end| start + end + 1) else {
+        bail!(
+            "Could not find the end of the list of days in {}",
+            path.display()
+        );
+    };
+
+    let mut offset = start
+        + source[start..]
+            .find('\n')
+            .context("Unexpected registry format")?
+        + 1;
+    let mut insert_at = offset;
+    for line in source[offset..end].split_inclusive('\n') {
+        let entry = line
+            .trim_start()
+            .strip_prefix("day!(")
+            .and_then(|entry| entry.split_once(','))
+            .and_then(|(number, _)| number.trim().parse::<u8>().ok());
+        match entry {
+            Some(number) if number == day => return Ok(false),
+            Some(number) if number < day => insert_at = offset + line.len(),
+            _ => {}
+        }
+        offset += line.len();
+    }
+
+    let mut source = source;
+    source.insert_str(insert_at, &format!("    day!({day}, {name}::Day{day}),\n"));
+    fs::write(path, source)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    fn workspace() -> anyhow::Result<tempfile::TempDir> {
+        let root = tempfile::tempdir()?;
+        let write = |path: &str, contents: &str| -> anyhow::Result<()> {
+            let path = root.path().join(path);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, contents)?;
+            Ok(())
+        };
+
+        write(
+            "Cargo.toml",
+            "[workspace]\nmembers = [\n    \"day1\",\n    \"day3\",\n    \"template\",\n    \"aoc\"\n]\n",
+        )?;
+        write(
+            "template/Cargo.toml",
+            "[package]\nname = \"template\" # Replaced\n",
+        )?;
+        write("template/src/lib.rs", "pub struct Template;\n")?;
+        write(
+            "aoc/Cargo.toml",
+            "[dependencies]\nday1 = { path = \"../day1\" }\n",
+        )?;
+        write(
+            "aoc/src/registry.rs",
+            "pub static DAYS: &[Day] = &[\n    day!(1, day1::Day1),\n    day!(3, day3::Day3),\n];\n",
+        )?;
+        Ok(root)
+    }
+
+    fn read(root: &Path, path: &str) -> String {
+        fs::read_to_string(root.join(path)).unwrap()
+    }
+
+    #[test]
+    fn test_new_day() -> anyhow::Result<()> {
+        let root = workspace()?;
+        let root = root.path();
+
+        let changes = crate::scaffold::new_day(root, 2)?;
+        assert_eq!(changes.len(), 5);
+        assert_eq!(read(root, "inputs/day2/default.txt"), "");
+
+        assert_eq!(
+            read(root, "day2/Cargo.toml"),
+            "[package]\nname = \"day2\" # Replaced\n"
+        );
+        assert_eq!(read(root, "day2/src/lib.rs"), "pub struct Day2;\n");
+        assert_eq!(
+            read(root, "Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"day1\",\n    \"day2\",\n    \"day3\",\n    \"template\",\n    \"aoc\"\n]\n"
+        );
+        assert_eq!(
+            read(root, "aoc/Cargo.toml"),
+            "[dependencies]\nday1 = { path = \"../day1\" }\nday2 = { path = \"../day2\" }\n"
+        );
+        assert_eq!(
+            read(root, "aoc/src/registry.rs"),
+            "pub static DAYS: &[Day] = &[\n    day!(1, day1::Day1),\n    day!(2, day2::Day2),\n    day!(3, day3::Day3),\n];\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_existing_day() -> anyhow::Result<()> {
+        let root = workspace()?;
+        let root = root.path();
+        fs::create_dir_all(root.join("day3/src"))?;
+        fs::write(root.join("day3/src/lib.rs"), "// Solved")?;
+
+        let changes = crate::scaffold::new_day(root, 3)?;
+        assert_eq!(
+            changes,
+            [
+                "day3 already exists, left untouched",
+                "Created an empty default input, paste the puzzle input in inputs/day3/default.txt",
+                "Added day3 to the runner dependencies"
+            ]
+        );
+        assert_eq!(read(root, "day3/src/lib.rs"), "// Solved");
+
+        crate::scaffold::new_day(root, 4)?;
+        let registry = read(root, "aoc/src/registry.rs");
+        let members = read(root, "Cargo.toml");
+        assert_eq!(
+            crate::scaffold::new_day(root, 4)?,
+            ["day4 already exists, left untouched"]
+        );
+        assert_eq!(read(root, "aoc/src/registry.rs"), registry);
+        assert_eq!(read(root, "Cargo.toml"), members);
+        Ok(())
+    }
+}
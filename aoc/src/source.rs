@@ -0,0 +1,127 @@
+use anyhow::{bail, Context};
+use flate2::read::MultiGzDecoder;
+use std::{
+    fs,
+    io::{self, Read},
+};
+
+/// Path standing for the standard input.
+pub const STDIN: &str = "-";
+
+/// First bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Expands every glob pattern among `paths`, in order, each pattern listing its matches sorted.
+/// Plain paths and [`STDIN`] are kept as is, while a pattern matching nothing is an error so a
+/// typo never silently runs nothing.
+pub fn expand(paths: &[String]) -> anyhow::Result<Vec<String>> {
+    let mut expanded = Vec::new();
+    for path in paths {
+        if !path.contains(['*', '?', '[']) {
+            expanded.push(path.clone());
+            continue;
+        }
+
+        let mut matches = glob::glob(path)
+            .with_context(|| format!("Invalid pattern {path}"))?
+            .map(|entry| Ok(entry?.display().to_string()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if matches.is_empty() {
+            bail!("No input matches {path}");
+        }
+        matches.sort();
+        expanded.extend(matches);
+    }
+
+    if expanded.iter().filter(|path| *path == STDIN).count() > 1 {
+        bail!("The standard input can only be read once");
+    }
+    Ok(expanded)
+}
+
+/// Reads a whole input from a file, or from the standard input for [`STDIN`].
+pub fn read(path: &str) -> anyhow::Result<String> {
+    let bytes = if path == STDIN {
+        let mut bytes = Vec::new();
+        io::stdin()
+            .lock()
+            .read_to_end(&mut bytes)
+            .context("Could not read the standard input")?;
+        bytes
+    } else {
+        fs::read(path).with_context(|| format!("Could not read {path}"))?
+    };
+    decode(bytes).with_context(|| format!("Invalid input {path}"))
+}
+
+/// Decompresses gzip inputs, recognized by their content rather than their name so piped ones
+/// work too.
+fn decode(bytes: Vec<u8>) -> anyhow::Result<String> {
+    let bytes = if bytes.starts_with(&GZIP_MAGIC) {
+        let mut decompressed = Vec::new();
+        MultiGzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut decompressed)
+            .context("Could not decompress the gzip stream")?;
+        decompressed
+    } else {
+        bytes
+    };
+    String::from_utf8(bytes).context("The input is not valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use flate2::{write::GzEncoder, Compression};
+    use std::{fs, io::Write};
+
+    #[test]
+    fn test_expand() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        for name in ["b.txt", "a.txt", "c.md"] {
+            fs::write(dir.path().join(name), "")?;
+        }
+        let root = dir.path().display();
+
+        let paths = super::expand(&[
+            format!("{root}/*.txt"),
+            "-".to_owned(),
+            "missing.txt".to_owned(),
+        ])?;
+        assert_eq!(
+            paths,
+            [
+                format!("{root}/a.txt"),
+                format!("{root}/b.txt"),
+                "-".to_owned(),
+                "missing.txt".to_owned(),
+            ]
+        );
+
+        assert!(super::expand(&[format!("{root}/*.gz")]).is_err());
+        assert!(super::expand(&["-".to_owned(), "-".to_owned()]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let plain = dir.path().join("plain.txt");
+        fs::write(&plain, "1000\n2000\n")?;
+        assert_eq!(super::read(&plain.display().to_string())?, "1000\n2000\n");
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"1000\n2000\n")?;
+        let compressed = dir.path().join("stress.txt.gz");
+        fs::write(&compressed, encoder.finish()?)?;
+        assert_eq!(
+            super::read(&compressed.display().to_string())?,
+            "1000\n2000\n"
+        );
+
+        fs::write(&plain, [0xff, 0xfe])?;
+        assert!(super::read(&plain.display().to_string()).is_err());
+        assert!(super::read("missing.txt").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,394 @@
+use crate::{config::Config, registry::Day, remote::Client, toml_file::TomlFile};
+use anyhow::{bail, Context};
+use std::{
+    fmt::{self, Display},
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use toml_edit::{ArrayOfTables, Item, Table};
+
+/// Name of the submission history, in the inputs directory.
+pub const FILE: &str = "history.toml";
+
+/// How the website judged a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted too soon after a previous attempt, the answer was not checked.
+    Wait,
+    /// The part was already solved, the answer was not checked.
+    Solved,
+}
+
+impl Verdict {
+    const NAMES: [(Verdict, &'static str); 6] = [
+        (Verdict::Right, "right"),
+        (Verdict::Wrong, "wrong"),
+        (Verdict::TooHigh, "too high"),
+        (Verdict::TooLow, "too low"),
+        (Verdict::Wait, "wait"),
+        (Verdict::Solved, "already solved"),
+    ];
+
+    /// Reads the verdict from the page returned after posting an answer.
+    pub fn from_page(page: &str) -> anyhow::Result<Self> {
+        Ok(if page.contains("That's the right answer") {
+            Verdict::Right
+        } else if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if page.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::Wait
+        } else if page.contains("You don't seem to be solving the right level") {
+            Verdict::Solved
+        } else {
+            bail!("Unrecognized answer page:\n{}", message(page))
+        })
+    }
+
+    fn is_wrong(self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, name) = Self::NAMES
+            .iter()
+            .find(|(verdict, _)| verdict == self)
+            .unwrap();
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(_, name)| *name == s)
+            .map(|(verdict, _)| *verdict)
+            .with_context(|| format!("Unknown verdict `{s}`"))
+    }
+}
+
+/// Text of the main article of a page, without its markup.
+pub fn message(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, article)| article.split_once('>'))
+        .map_or(page, |(_, article)| article);
+    let article = article
+        .split_once("</article>")
+        .map_or(article, |(article, _)| article);
+
+    crate::page::text(article)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A single submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub time: i64,
+}
+
+/// Every answer ever submitted, keyed by day and part.
+///
+/// ```toml
+/// [[day1.part1]]
+/// answer = "68442"
+/// verdict = "too low"
+/// time = 1669871012
+/// ```
+pub struct History(TomlFile);
+
+impl History {
+    /// Loads the history, a missing file has no attempts.
+    pub fn load(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        TomlFile::load(path, "submission history").map(Self)
+    }
+
+    pub fn attempts(&self, day: u8, part: u8) -> anyhow::Result<Vec<Attempt>> {
+        let Some(attempts) = self
+            .0
+            .document
+            .get(&format!("day{day}"))
+            .and_then(|day| day.get(format!("part{part}")))
+        else {
+            return Ok(Vec::new());
+        };
+
+        attempts
+            .as_array_of_tables()
+            .context("Attempts of a part must be an array of tables")?
+            .iter()
+            .map(|attempt| {
+                Ok(Attempt {
+                    answer: attempt
+                        .get("answer")
+                        .and_then(Item::as_str)
+                        .context("Attempt has no answer")?
+                        .to_owned(),
+                    verdict: attempt
+                        .get("verdict")
+                        .and_then(Item::as_str)
+                        .context("Attempt has no verdict")?
+                        .parse()?,
+                    time: attempt
+                        .get("time")
+                        .and_then(Item::as_integer)
+                        .context("Attempt has no time")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Records an attempt, call [`History::save`] to persist it.
+    pub fn record(&mut self, day: u8, part: u8, attempt: &Attempt) -> anyhow::Result<()> {
+        let attempts = self
+            .0
+            .day_table_mut(day)?
+            .entry(&format!("part{part}"))
+            .or_insert_with(|| Item::ArrayOfTables(ArrayOfTables::new()))
+            .as_array_of_tables_mut()
+            .context("Attempts of a part must be an array of tables")?;
+
+        let mut table = Table::new();
+        table.insert("answer", toml_edit::value(&attempt.answer));
+        table.insert("verdict", toml_edit::value(attempt.verdict.to_string()));
+        table.insert("time", toml_edit::value(attempt.time));
+        attempts.push(table);
+        Ok(())
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        self.0.save()
+    }
+}
+
+/// Refuses answers the previous attempts already rule out: the part was solved, the same answer
+/// was judged wrong, or a number is not strictly between the known too low and too high answers.
+pub fn check(attempts: &[Attempt], answer: &str) -> anyhow::Result<()> {
+    let number = answer.parse::<i128>().ok();
+
+    for attempt in attempts {
+        if attempt.verdict == Verdict::Right {
+            bail!("Already solved, the answer was {}", attempt.answer);
+        }
+        if attempt.verdict.is_wrong() && attempt.answer == answer {
+            bail!("{answer} was already submitted and was {}", attempt.verdict);
+        }
+
+        let (Some(number), Ok(bound)) = (number, attempt.answer.parse::<i128>()) else {
+            continue;
+        };
+        match attempt.verdict {
+            Verdict::TooHigh if number >= bound => {
+                bail!("{answer} is not below {bound}, which is already too high")
+            }
+            Verdict::TooLow if number <= bound => {
+                bail!("{answer} is not above {bound}, which is already too low")
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Outcome of [`submit`].
+#[derive(Debug)]
+pub struct Submission {
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Explanation given by the website.
+    pub message: String,
+}
+
+/// Solves `part` of `day` on its default input and submits the answer, unless the history already
+/// rules it out. Every submitted answer is recorded in the history.
+pub fn submit(config: &Config, day: &Day, part: u8) -> anyhow::Result<Submission> {
+    let input = day.input(&config.inputs)?;
+    let run = day.puzzle.solve(&input, &[part])?;
+    let answer = run
+        .answers
+        .into_iter()
+        .next()
+        .context("No answer was computed")?
+        .value?;
+
+    let mut history = History::load(config.inputs.join(FILE))?;
+    check(&history.attempts(day.day, part)?, &answer)
+        .with_context(|| format!("Refusing to submit {answer}"))?;
+
+    let page = Client::new(config)?.submit(day.day, part, &answer)?;
+    let verdict = Verdict::from_page(&page)?;
+    history.record(
+        day.day,
+        part,
+        &Attempt {
+            answer: answer.clone(),
+            verdict,
+            time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64,
+        },
+    )?;
+    history.save()?;
+
+    Ok(Submission {
+        answer,
+        verdict,
+        message: message(&page),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Attempt, Verdict};
+
+    fn page(text: &str) -> String {
+        format!("<html><main>\n<article><p>{text}</p></article>\n</main></html>")
+    }
+
+    fn attempt(answer: &str, verdict: Verdict) -> Attempt {
+        Attempt {
+            answer: answer.to_owned(),
+            verdict,
+            time: 0,
+        }
+    }
+
+    #[test]
+    fn test_verdict() -> anyhow::Result<()> {
+        let wrong = page(
+            "That's not the right answer; your answer is too high. Please wait one minute before \
+             trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>",
+        );
+        assert_eq!(Verdict::from_page(&wrong)?, Verdict::TooHigh);
+        assert_eq!(
+            super::message(&wrong),
+            "That's not the right answer; your answer is too high. Please wait one minute before \
+             trying again. [Return to Day 1]"
+        );
+
+        let wait = page("You gave an answer too recently; you have <em>42s</em> left to wait.");
+        assert_eq!(Verdict::from_page(&wait)?, Verdict::Wait);
+        assert_eq!(
+            super::message(&wait),
+            "You gave an answer too recently; you have 42s left to wait."
+        );
+
+        assert_eq!(
+            Verdict::from_page(&page(
+                "That's the right answer! You are one gold star closer."
+            ))?,
+            Verdict::Right
+        );
+        assert!(Verdict::from_page(&page("Puzzle inputs differ by user.")).is_err());
+
+        for (verdict, _) in Verdict::NAMES {
+            assert_eq!(verdict.to_string().parse::<Verdict>()?, verdict);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_check() {
+        let attempts = [
+            attempt("100", Verdict::TooLow),
+            attempt("abc", Verdict::Wrong),
+            attempt("500", Verdict::TooHigh),
+            attempt("300", Verdict::Wait),
+        ];
+
+        assert!(super::check(&attempts, "300").is_ok());
+        assert!(super::check(&attempts, "101").is_ok());
+        assert_eq!(
+            super::check(&attempts, "abc").unwrap_err().to_string(),
+            "abc was already submitted and was wrong"
+        );
+        assert_eq!(
+            super::check(&attempts, "500").unwrap_err().to_string(),
+            "500 was already submitted and was too high"
+        );
+        assert_eq!(
+            super::check(&attempts, "90").unwrap_err().to_string(),
+            "90 is not above 100, which is already too low"
+        );
+        assert_eq!(
+            super::check(&attempts, "1000").unwrap_err().to_string(),
+            "1000 is not below 500, which is already too high"
+        );
+        assert_eq!(
+            super::check(&[attempt("240", Verdict::Right)], "300")
+                .unwrap_err()
+                .to_string(),
+            "Already solved, the answer was 240"
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "embedded-inputs"))]
+    fn test_submit() -> anyhow::Result<()> {
+        use super::History;
+        use crate::{registry, remote::tests::server};
+        use std::fs;
+
+        let dir = tempfile::tempdir()?;
+        fs::create_dir_all(dir.path().join("day1"))?;
+        fs::write(
+            dir.path().join("day1").join("default.txt"),
+            "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n",
+        )?;
+        let (base_url, requests) = server(|request| {
+            let text = match request.body.as_str() {
+                "level=1&answer=24000" => "That's not the right answer; your answer is too low.",
+                "level=2&answer=45000" => "That's the right answer!",
+                _ => "That's not the right answer.",
+            };
+            (200, page(text))
+        });
+        let config = crate::remote::tests::config(dir.path(), base_url);
+        let day = registry::find(1).unwrap();
+
+        let submission = super::submit(&config, day, 1)?;
+        assert_eq!(submission.answer, "24000");
+        assert_eq!(submission.verdict, Verdict::TooLow);
+        let error = super::submit(&config, day, 1).unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "Refusing to submit 24000: 24000 was already submitted and was too low"
+        );
+
+        assert_eq!(super::submit(&config, day, 2)?.verdict, Verdict::Right);
+        {
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests.len(), 2);
+            assert_eq!(requests[1].method, "POST");
+            assert_eq!(requests[1].url, "/2022/day/1/answer");
+            assert_eq!(requests[1].cookie.as_deref(), Some("session=secret"));
+        }
+
+        let history = History::load(dir.path().join(super::FILE))?;
+        let attempts = history.attempts(1, 1)?;
+        assert_eq!(attempts.len(), 1);
+        assert_eq!(
+            (attempts[0].answer.as_str(), attempts[0].verdict),
+            ("24000", Verdict::TooLow)
+        );
+        assert_eq!(history.attempts(1, 2)?[0].verdict, Verdict::Right);
+        assert!(history.attempts(2, 1)?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use anyhow::Context;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use toml_edit::{Document, Item, Table};
+
+/// TOML file of the runner, edited in place so hand written comments and formatting survive. A
+/// missing file reads as empty and is only created when saved.
+pub struct TomlFile {
+    path: PathBuf,
+    pub document: Document,
+}
+
+impl TomlFile {
+    /// Loads the file at `path`, described as `what` when it is invalid.
+    pub fn load(path: impl Into<PathBuf>, what: &str) -> anyhow::Result<Self> {
+        let path = path.into();
+        let document = match fs::read_to_string(&path) {
+            Ok(contents) => contents
+                .parse()
+                .with_context(|| format!("Invalid {what} {}", path.display()))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Document::new(),
+            Err(error) => return Err(error.into()),
+        };
+        Ok(Self { path, document })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Table holding everything about `day`, created on first use. A new one is implicit so a
+    /// day holding only tables gets no `[dayN]` header of its own.
+    pub fn day_table_mut(&mut self, day: u8) -> anyhow::Result<&mut Table> {
+        self.document
+            .as_table_mut()
+            .entry(&format!("day{day}"))
+            .or_insert_with(|| {
+                let mut table = Table::new();
+                table.set_implicit(true);
+                Item::Table(table)
+            })
+            .as_table_mut()
+            .with_context(|| format!("day{day} must be a table in {}", self.path.display()))
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        fs::write(&self.path, self.document.to_string())
+            .with_context(|| format!("Could not write {}", self.path.display()))
+    }
+}
@@ -0,0 +1,28 @@
+use anyhow::Context;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Finds the root of the workspace, looking up from the current directory first and falling back
+/// to the workspace this binary was built from.
+pub fn root() -> anyhow::Result<PathBuf> {
+    let current = env::current_dir().context("Could not read the current directory")?;
+    if let Some(root) = current.ancestors().find(|path| is_root(path)) {
+        return Ok(root.to_owned());
+    }
+
+    let built = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    if is_root(&built) {
+        Ok(built)
+    } else {
+        anyhow::bail!("Not inside the aoc workspace")
+    }
+}
+
+fn is_root(path: &Path) -> bool {
+    fs::read_to_string(path.join("Cargo.toml"))
+        .ok()
+        .and_then(|manifest| manifest.parse::<toml_edit::Document>().ok())
+        .is_some_and(|manifest| manifest.contains_key("workspace"))
+}
@@ -0,0 +1,164 @@
+use nom::Offset;
+use nom_supreme::error::{BaseErrorKind, ErrorTree, Expectation, StackContext};
+use std::fmt::{self, Display};
+
+/// Human readable parse failure, pointing at the furthest position any parser reached along with
+/// everything that was expected there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the failure.
+    pub line: usize,
+    /// 1-based column of the failure, in characters.
+    pub column: usize,
+    /// Full text of the offending line.
+    pub source_line: String,
+    /// Every alternative that would have been accepted at the failure point.
+    pub expected: Vec<String>,
+}
+
+impl ParseError {
+    /// Renders an error tree whose locations are tails of `input`.
+    pub fn new(input: &str, tree: &ErrorTree<&str>) -> Self {
+        let mut leaves = Vec::new();
+        collect(input, tree, None, &mut leaves);
+
+        let offset = leaves.iter().map(|(offset, _)| *offset).max().unwrap_or(0);
+        let mut expected = Vec::new();
+        for (_, description) in leaves.into_iter().filter(|(at, _)| *at == offset) {
+            if !expected.contains(&description) {
+                expected.push(description);
+            }
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |index| offset + index);
+
+        Self {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            source_line: input[line_start..line_end].to_owned(),
+            expected,
+        }
+    }
+}
+
+/// Flattens the error tree into `(offset, description)` leaves. A context label replaces the
+/// description of the errors it wraps when it was attached at the very same position, so
+/// `tag("   ").context("three spaces")` reads as "three spaces" rather than `"   "`.
+fn collect(
+    input: &str,
+    tree: &ErrorTree<&str>,
+    label: Option<(usize, &'static str)>,
+    leaves: &mut Vec<(usize, String)>,
+) {
+    match tree {
+        ErrorTree::Base { location, kind } => {
+            let offset = input.offset(location);
+            let description = match (label, kind) {
+                (Some((at, label)), _) if at == offset => label.to_owned(),
+                (_, BaseErrorKind::Expected(expectation)) => describe(expectation),
+                (_, BaseErrorKind::Kind(kind)) => format!("{kind:?}"),
+                (_, BaseErrorKind::External(error)) => error.to_string(),
+            };
+            leaves.push((offset, description));
+        }
+        ErrorTree::Stack { base, contexts } => {
+            let label = contexts
+                .iter()
+                .find_map(|(location, context)| match context {
+                    StackContext::Context(label) => Some((input.offset(location), *label)),
+                    StackContext::Kind(..) => None,
+                })
+                .or(label);
+            collect(input, base, label, leaves);
+        }
+        ErrorTree::Alt(siblings) => {
+            for sibling in siblings {
+                collect(input, sibling, label, leaves);
+            }
+        }
+    }
+}
+
+fn describe(expectation: &Expectation) -> String {
+    match expectation {
+        Expectation::Tag(tag) => format!("`{tag}`"),
+        Expectation::Char('\n') => "a newline".to_owned(),
+        Expectation::Char(c) => format!("`{}`", c.escape_debug()),
+        Expectation::Eof => "the end of the input".to_owned(),
+        other => other.to_string(),
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to parse input at line {}, column {}",
+            self.line, self.column
+        )?;
+        if let Some((last, rest)) = self.expected.split_last() {
+            write!(f, ": expected ")?;
+            if !rest.is_empty() {
+                write!(f, "{} or ", rest.join(", "))?;
+            }
+            write!(f, "{last}")?;
+        }
+
+        let gutter = self.line.to_string().len();
+        writeln!(f)?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{:gutter$} | {:>column$}", "", "^", column = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        branch::alt,
+        bytes::complete::tag,
+        character::complete::{anychar, char},
+        sequence::{delimited, separated_pair},
+        Parser,
+    };
+    use nom_supreme::ParserExt;
+
+    fn cell<'i>() -> impl Parser<&'i str, Option<char>, nom_supreme::error::ErrorTree<&'i str>> {
+        alt((
+            delimited(char('['), anychar, char(']')).map(Some),
+            tag("   ").value(None).context("three spaces"),
+        ))
+    }
+
+    #[test]
+    fn test_expected_alternatives() {
+        let error =
+            crate::parse("[A]\n{B}", separated_pair(cell(), char('\n'), cell())).unwrap_err();
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 1);
+        assert_eq!(error.source_line, "{B}");
+        assert_eq!(error.expected, ["`[`", "three spaces"]);
+    }
+
+    #[test]
+    fn test_display() {
+        let error =
+            crate::parse("[A] [B\n", separated_pair(cell(), char(' '), cell())).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            indoc::indoc! {"
+                failed to parse input at line 1, column 7: expected `]`
+                  |
+                1 | [A] [B
+                  |       ^"
+            }
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt::{self, Display};
+
+/// Intermediate data behind an answer, built by [`Solution::explain`](crate::Solution::explain)
+/// as nested sections of lines and rendered with two spaces of indentation per level.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// Every line along with its depth.
+    lines: Vec<(usize, String)>,
+    depth: usize,
+}
+
+impl Explanation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a line at the current depth, text spanning several lines is indented as a whole.
+    pub fn line(&mut self, text: impl Display) -> &mut Self {
+        let text = text.to_string();
+        self.lines
+            .extend(text.lines().map(|line| (self.depth, line.to_owned())));
+        self
+    }
+
+    /// Adds a heading, then every line added by `body` one level deeper.
+    pub fn section(
+        &mut self,
+        heading: impl Display,
+        body: impl FnOnce(&mut Self) -> anyhow::Result<()>,
+    ) -> anyhow::Result<&mut Self> {
+        self.line(heading);
+        self.depth += 1;
+        let result = body(self);
+        self.depth -= 1;
+        result.map(|()| self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (depth, line) in &self.lines {
+            writeln!(f, "{:width$}{line}", "", width = depth * 2)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Explanation;
+
+    #[test]
+    fn test_sections() -> anyhow::Result<()> {
+        let mut explanation = Explanation::new();
+        assert!(explanation.is_empty());
+
+        explanation.section("Stacks", |explanation| {
+            explanation.line("[D]\n[N] [C]");
+            explanation.section("Move 1", |explanation| {
+                explanation.line("done");
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+        explanation.line("Top crates CMZ");
+        assert_eq!(
+            explanation.to_string(),
+            "Stacks\n  [D]\n  [N] [C]\n  Move 1\n    done\nTop crates CMZ\n"
+        );
+
+        let error = explanation.section("Failing", |_| anyhow::bail!("Stack was empty"));
+        assert!(error.is_err());
+        explanation.line("Back at the top");
+        assert!(explanation
+            .to_string()
+            .ends_with("Failing\nBack at the top\n"));
+        Ok(())
+    }
+}
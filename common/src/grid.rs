@@ -0,0 +1,459 @@
+use crate::lines;
+use anyhow::{anyhow, bail};
+use std::{
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Index, IndexMut, Sub},
+    str::FromStr,
+};
+
+/// Position of a cell, `x` growing rightwards along a row and `y` downwards from the first row.
+/// Points outside of a grid are valid, they just don't index anything.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Adjacent point in `direction`.
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// Points sharing an edge with this one, clockwise from the one above.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Direction::CARDINAL
+            .iter()
+            .map(move |&direction| self.step(direction))
+    }
+
+    /// Points sharing an edge or a corner with this one, clockwise from the one above.
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Direction::CLOCKWISE
+            .iter()
+            .map(move |&direction| self.step(direction))
+    }
+
+    pub fn manhattan(self, other: Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+crate::nom_enum! {
+    /// Way from a cell to one of its eight neighbours, parsed from the letters, arrows and
+    /// compass points puzzles use.
+    pub enum Direction {
+        Up = "U" | "^" | "N",
+        UpRight = "NE",
+        Right = "R" | ">" | "E",
+        DownRight = "SE",
+        Down = "D" | "v" | "S",
+        DownLeft = "SW",
+        Left = "L" | "<" | "W",
+        UpLeft = "NW",
+    }
+}
+
+impl Direction {
+    /// Directions towards the neighbours sharing an edge, clockwise from up.
+    pub const CARDINAL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Directions towards every neighbour, clockwise from up.
+    pub const CLOCKWISE: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Move from a point to its neighbour in this direction.
+    pub fn offset(self) -> Point {
+        match self {
+            Self::Up => Point::new(0, -1),
+            Self::UpRight => Point::new(1, -1),
+            Self::Right => Point::new(1, 0),
+            Self::DownRight => Point::new(1, 1),
+            Self::Down => Point::new(0, 1),
+            Self::DownLeft => Point::new(-1, 1),
+            Self::Left => Point::new(-1, 0),
+            Self::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    fn turn(self, eighths: usize) -> Self {
+        let index = Self::CLOCKWISE
+            .iter()
+            .position(|&direction| direction == self)
+            .unwrap_or_default();
+        Self::CLOCKWISE[(index + eighths) % 8]
+    }
+
+    /// Quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        self.turn(2)
+    }
+
+    /// Quarter turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.turn(6)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn(4)
+    }
+}
+
+/// Rectangular block of cells, stored row after row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid of `width` columns holding `cells` row after row.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> anyhow::Result<Self> {
+        if !cells.len().is_multiple_of(width) {
+            bail!("{} cells can't be split in rows of {width}", cells.len());
+        }
+        Ok(Self {
+            width,
+            height: cells.len().checked_div(width).unwrap_or_default(),
+            cells,
+        })
+    }
+
+    /// Parses a block of characters, one row per line, converting each character with `cell`.
+    /// Every line must be as long as the first one.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Self> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        for line in lines(input) {
+            let before = cells.len();
+            for c in line.text.chars() {
+                cells.push(cell(c).map_err(|error| line.error(error))?);
+            }
+            let length = cells.len() - before;
+            match width {
+                None => width = Some(length),
+                Some(width) if width != length => {
+                    let reason = anyhow!("Expected a row of {width} cells, found {length}");
+                    return Err(line.error(reason).into());
+                }
+                Some(_) => {}
+            }
+        }
+        Self::from_cells(width.unwrap_or_default(), cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `point` is one of the cells.
+    pub fn contains(&self, point: Point) -> bool {
+        self.offset(point).is_some()
+    }
+
+    fn offset(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(point.y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn point(&self, offset: usize) -> Point {
+        Point::new((offset % self.width) as i64, (offset / self.width) as i64)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Every cell along with its position, row after row.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    /// Every position, row after row.
+    pub fn points(&self) -> impl '_ + Iterator<Item = Point> {
+        (0..self.cells.len()).map(|offset| self.point(offset))
+    }
+
+    /// Neighbours of `point` sharing an edge with it and inside the grid.
+    pub fn neighbours4(&self, point: Point) -> impl '_ + Iterator<Item = (Point, &T)> {
+        self.cells_at(point.neighbours4())
+    }
+
+    /// Neighbours of `point` sharing an edge or a corner with it and inside the grid.
+    pub fn neighbours8(&self, point: Point) -> impl '_ + Iterator<Item = (Point, &T)> {
+        self.cells_at(point.neighbours8())
+    }
+
+    fn cells_at<'g>(
+        &'g self,
+        points: impl 'g + Iterator<Item = Point>,
+    ) -> impl 'g + Iterator<Item = (Point, &'g T)> {
+        points.filter_map(move |point| Some((point, self.get(point)?)))
+    }
+
+    /// Cells met walking from `from` in `direction`, `from` excluded, up to the edge. Walking
+    /// diagonally gives the diagonals of the grid.
+    pub fn ray(&self, from: Point, direction: Direction) -> impl '_ + Iterator<Item = (Point, &T)> {
+        let mut point = from;
+        std::iter::from_fn(move || {
+            point += direction.offset();
+            Some((point, self.get(point)?))
+        })
+    }
+
+    /// Cells of row `y`, left to right.
+    pub fn row(&self, y: usize) -> impl '_ + Iterator<Item = &T> {
+        self.cells
+            .get(y * self.width..(y + 1) * self.width)
+            .unwrap_or_default()
+            .iter()
+    }
+
+    /// Cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl '_ + Iterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// Applies `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Builds a grid of the given size, each cell of it taken from this one at `source`.
+    fn rearranged(&self, width: usize, height: usize, source: impl Fn(Point) -> Point) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height as i64)
+            .flat_map(|y| (0..width as i64).map(move |x| Point::new(x, y)))
+            .map(|point| self[source(point)].clone())
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Mirror image along the diagonal from the top left corner, rows becoming columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearranged(self.height, self.width, |point| {
+            Point::new(point.y, point.x)
+        })
+    }
+
+    /// Quarter turn clockwise, the first column becoming the first row read bottom up.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last_row = self.height as i64 - 1;
+        self.rearranged(self.height, self.width, |point| {
+            Point::new(point.y, last_row - point.x)
+        })
+    }
+
+    /// Quarter turn counterclockwise, the last column becoming the first row.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        let last_column = self.width as i64 - 1;
+        self.rearranged(self.height, self.width, |point| {
+            Point::new(last_column - point.y, point.x)
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        let (width, height) = (self.width, self.height);
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point} is outside of the {width}x{height} grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point} is outside of the {width}x{height} grid"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> anyhow::Result<Self> {
+        Self::parse(input, Ok)
+    }
+}
+
+/// One line per row, the cells written next to each other.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Grid, Point};
+    use anyhow::Context;
+
+    const BLOCK: &str = "abc\ndef\n";
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        let grid: Grid<char> = BLOCK.parse()?;
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.to_string(), BLOCK);
+
+        let digits = Grid::parse("30373\n25512\n", |c| {
+            c.to_digit(10).context("Expected a digit")
+        })?;
+        assert_eq!(digits.row(1).copied().collect::<Vec<_>>(), [2, 5, 5, 1, 2]);
+        assert_eq!(digits.column(4).copied().collect::<Vec<_>>(), [3, 2]);
+        assert_eq!(digits.column(5).count(), 0);
+
+        let error = "abc\nde\n".parse::<Grid<char>>().unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("invalid input on line 2: Expected a row of 3"));
+        assert!(Grid::parse("1a", |c| c.to_digit(10).context("Expected a digit")).is_err());
+        assert!(Grid::from_cells(2, vec![1, 2, 3]).is_err());
+        assert!(Grid::from_cells(0, vec![1]).is_err());
+        let empty = "".parse::<Grid<char>>()?;
+        assert_eq!((empty.height(), empty.iter().count()), (0, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbours() -> anyhow::Result<()> {
+        let grid: Grid<char> = BLOCK.parse()?;
+        let cells = |neighbours: Vec<(Point, &char)>| -> String {
+            neighbours.into_iter().map(|(_, c)| c).collect()
+        };
+
+        assert_eq!(cells(grid.neighbours4(Point::new(0, 0)).collect()), "bd");
+        assert_eq!(cells(grid.neighbours4(Point::new(1, 1)).collect()), "bfd");
+        assert_eq!(cells(grid.neighbours8(Point::new(1, 1)).collect()), "bcfda");
+        assert_eq!(Point::new(5, 5).neighbours8().count(), 8);
+
+        assert_eq!(
+            cells(grid.ray(Point::new(0, 0), Direction::Right).collect()),
+            "bc"
+        );
+        assert_eq!(
+            cells(grid.ray(Point::new(0, 0), Direction::DownRight).collect()),
+            "e"
+        );
+        assert_eq!(
+            cells(grid.ray(Point::new(2, 1), Direction::Up).collect()),
+            "c"
+        );
+        assert_eq!(grid.ray(Point::new(0, 0), Direction::Left).count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rearrange() -> anyhow::Result<()> {
+        let grid: Grid<char> = BLOCK.parse()?;
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(
+            grid.map(|c| c.to_ascii_uppercase()).to_string(),
+            "ABC\nDEF\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!("^".parse(), Ok(Direction::Up));
+        assert_eq!("NE".parse(), Ok(Direction::UpRight));
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::DownLeft.opposite(), Direction::UpRight);
+        assert_eq!(Point::new(1, 1).step(Direction::Left), Point::new(0, 1));
+        assert_eq!(Point::new(1, 1).manhattan(Point::new(-2, 3)), 5);
+    }
+}
@@ -0,0 +1,373 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Add, Range, RangeInclusive, Sub},
+};
+
+/// Integer type usable as the bounds of an [`Interval`].
+pub trait Endpoint: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+
+    /// Next value, turning an inclusive upper bound into an exclusive one.
+    fn successor(self) -> Self;
+    /// Next value, `None` for the maximum.
+    fn checked_successor(self) -> Option<Self>;
+    /// Previous value, turning an exclusive upper bound into an inclusive one.
+    fn predecessor(self) -> Self;
+}
+
+macro_rules! endpoint {
+    ($($type:ty),*) => {
+        $(
+            impl Endpoint for $type {
+                const ZERO: Self = 0;
+
+                fn successor(self) -> Self {
+                    self.checked_successor().expect("No value after the maximum")
+                }
+
+                fn checked_successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn predecessor(self) -> Self {
+                    self.checked_sub(1).expect("No value before the minimum")
+                }
+            }
+        )*
+    };
+}
+
+endpoint!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Contiguous run of values, built either closed, both bounds included, or half-open, the end
+/// excluded. Both are stored half-open so they mix freely. An interval whose end is not after its
+/// start is empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Endpoint> Interval<T> {
+    /// Every value from `start` to `last`, both included.
+    ///
+    /// # Panics
+    ///
+    /// When `last` is the maximum of `T`, since the end would not fit. See
+    /// [`checked_closed`](Self::checked_closed) for untrusted bounds.
+    pub fn closed(start: T, last: T) -> Self {
+        Self {
+            start,
+            end: last.successor(),
+        }
+    }
+
+    /// [`closed`](Self::closed), or `None` when `last` is the maximum of `T`.
+    pub fn checked_closed(start: T, last: T) -> Option<Self> {
+        Some(Self {
+            start,
+            end: last.checked_successor()?,
+        })
+    }
+
+    /// Every value from `start` included to `end` excluded.
+    pub fn half_open(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    /// Exclusive upper bound.
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// Inclusive upper bound, `None` when empty.
+    pub fn last(&self) -> Option<T> {
+        (!self.is_empty()).then(|| self.end.predecessor())
+    }
+
+    /// Number of values in the interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::ZERO
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Whether every value of `other` is in this interval, always true for an empty `other`.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        other.is_empty() || self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether both intervals share at least one value.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Values in both intervals, `None` when they have none in common.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        };
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Values in either interval, `None` when they are neither overlapping nor adjacent since
+    /// the result would not be contiguous. An empty interval leaves the other one unchanged.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        (self.start <= other.end && other.start <= self.end).then(|| Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        })
+    }
+}
+
+impl<T: Endpoint> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::half_open(range.start, range.end)
+    }
+}
+
+impl<T: Endpoint> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (start, last) = range.into_inner();
+        Self::closed(start, last)
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// Union of any number of intervals, kept as sorted, disjoint and non adjacent intervals so
+/// queries only need a binary search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Endpoint> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every value of `interval`, merging it with the intervals it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        // Every interval from `first` to `last` excluded overlaps or touches the new one.
+        let first = self
+            .intervals
+            .partition_point(|existing| existing.end < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|existing| existing.start <= interval.end);
+
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, existing| {
+                merged.union(existing).unwrap_or(merged)
+            });
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Whether `value` is covered by any interval.
+    pub fn contains(&self, value: T) -> bool {
+        self.find(value).is_some()
+    }
+
+    /// Whether every value of `interval` is covered.
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        interval.is_empty()
+            || self
+                .find(interval.start)
+                .is_some_and(|covering| covering.contains_interval(interval))
+    }
+
+    /// Interval covering `value`, if any.
+    pub fn find(&self, value: T) -> Option<&Interval<T>> {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+        self.intervals
+            .get(index)
+            .filter(|interval| interval.contains(value))
+    }
+
+    /// Number of values covered.
+    pub fn covered(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |total, interval| total + interval.len())
+    }
+
+    /// Number of values of `window` that are covered.
+    pub fn covered_within(&self, window: &Interval<T>) -> T {
+        let first = self
+            .intervals
+            .partition_point(|interval| interval.end <= window.start);
+        self.intervals[first..]
+            .iter()
+            .take_while(|interval| interval.start < window.end)
+            .filter_map(|interval| interval.intersection(window))
+            .fold(T::ZERO, |total, interval| total + interval.len())
+    }
+
+    /// Maximal runs of values in `window` that are not covered, in order.
+    pub fn gaps(&self, window: &Interval<T>) -> Vec<Interval<T>> {
+        let mut gaps = Vec::new();
+        let mut start = window.start;
+        for interval in &self.intervals {
+            if interval.end <= start {
+                continue;
+            }
+            if interval.start >= window.end {
+                break;
+            }
+            if interval.start > start {
+                gaps.push(Interval::half_open(start, interval.start));
+            }
+            start = interval.end;
+        }
+        if start < window.end {
+            gaps.push(Interval::half_open(start, window.end));
+        }
+        gaps
+    }
+
+    /// Merged intervals, sorted.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+}
+
+impl<T: Endpoint> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(intervals: I) -> Self {
+        let mut set = Self::new();
+        for interval in intervals {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Interval, IntervalSet};
+
+    #[test]
+    fn test_interval() {
+        let closed = Interval::closed(2, 4);
+        assert_eq!(closed, Interval::half_open(2, 5));
+        assert_eq!(closed, (2..=4).into());
+        assert_eq!(
+            (closed.start(), closed.end(), closed.last()),
+            (2, 5, Some(4))
+        );
+        assert_eq!(closed.len(), 3);
+        assert!(closed.contains(4) && !closed.contains(5));
+        assert_eq!(closed.to_string(), "[2, 5)");
+
+        let empty = Interval::half_open(3, 3);
+        assert!(empty.is_empty());
+        assert_eq!((empty.len(), empty.last()), (0, None));
+        assert_eq!(Interval::half_open(5, 2).len(), 0);
+
+        assert!(std::panic::catch_unwind(|| Interval::closed(0, u8::MAX)).is_err());
+        assert_eq!(Interval::checked_closed(0, u8::MAX), None);
+        assert_eq!(Interval::checked_closed(2, 4), Some(closed));
+
+        assert!(Interval::closed(2, 8).contains_interval(&Interval::closed(3, 7)));
+        assert!(!Interval::closed(3, 7).contains_interval(&Interval::closed(2, 8)));
+        assert!(closed.contains_interval(&empty));
+
+        assert!(Interval::closed(5, 7).overlaps(&Interval::closed(7, 9)));
+        assert!(!Interval::closed(2, 3).overlaps(&Interval::closed(4, 5)));
+        assert!(!Interval::half_open(2, 4).overlaps(&Interval::half_open(4, 6)));
+
+        assert_eq!(
+            Interval::closed(2, 6).intersection(&Interval::closed(4, 8)),
+            Some(Interval::closed(4, 6))
+        );
+        assert_eq!(
+            Interval::closed(2, 3).intersection(&Interval::closed(4, 5)),
+            None
+        );
+        assert_eq!(
+            Interval::closed(2, 3).union(&Interval::closed(4, 5)),
+            Some(Interval::closed(2, 5))
+        );
+        assert_eq!(Interval::closed(2, 3).union(&Interval::closed(5, 6)), None);
+        assert_eq!(
+            empty.union(&Interval::closed(8, 9)),
+            Some(Interval::closed(8, 9))
+        );
+    }
+
+    #[test]
+    fn test_set() {
+        let set: IntervalSet<i64> = [(12, 13), (-2, 2), (16, 24), (2, 2), (14, 18), (3, 4)]
+            .into_iter()
+            .map(|(start, last)| Interval::closed(start, last))
+            .collect();
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            [Interval::closed(-2, 4), Interval::closed(12, 24)]
+        );
+
+        assert_eq!(set.covered(), 20);
+        assert_eq!(set.covered_within(&Interval::closed(0, 20)), 14);
+        assert!(set.contains(-2) && set.contains(4) && !set.contains(5) && !set.contains(25));
+        assert_eq!(set.find(13), Some(&Interval::closed(12, 24)));
+        assert!(set.contains_interval(&Interval::closed(15, 20)));
+        assert!(!set.contains_interval(&Interval::closed(3, 12)));
+        assert_eq!(
+            set.gaps(&Interval::closed(0, 30)),
+            [Interval::closed(5, 11), Interval::closed(25, 30)]
+        );
+
+        let mut set = set;
+        set.insert(Interval::closed(5, 11));
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            [Interval::closed(-2, 24)]
+        );
+        set.insert(Interval::half_open(40, 40));
+        assert_eq!(set.iter().count(), 1);
+        assert!(IntervalSet::<u8>::new().is_empty());
+    }
+}
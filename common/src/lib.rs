@@ -1,51 +1,80 @@
+mod diagnostic;
+mod explain;
+pub mod grid;
+pub mod interval;
+mod line;
+mod nom_enum;
+mod normalize;
+pub mod search;
+
+pub use diagnostic::ParseError;
+pub use explain::Explanation;
+pub use line::{lines, parse_lines, Line, LineError};
 use nom::Parser;
-use nom_supreme::{
-    error::ErrorTree,
-    final_parser::{final_parser, Location},
-};
+pub use nom_enum::UnknownVariant;
+#[doc(hidden)]
+pub use nom_enum::{find_alias as __find_alias, parse_alias as __parse_alias};
+use nom_supreme::{error::ErrorTree, final_parser::final_parser};
+pub use normalize::normalize;
+use std::{convert::Infallible, fmt::Display};
+
+/// A day's puzzle, split into a parsing phase and one solving phase per part so runners,
+/// benchmarks and test harnesses can drive every day the same way.
+pub trait Solution {
+    /// Parsed puzzle input shared by both parts, may borrow from the raw input. Runners may solve
+    /// the parts concurrently, hence `Sync`.
+    type Input<'i>: Sync;
+    /// Answer produced by the first part.
+    type Answer1: Answer;
+    /// Answer produced by the second part, [`Infallible`] for a puzzle with a single part like
+    /// the last day of an event. Answers may span several lines, like letters drawn on a grid.
+    type Answer2: Answer;
+
+    /// Parses an input already brought to a common form by [`normalize`].
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>>;
+    fn part1(input: &Self::Input<'_>) -> anyhow::Result<Self::Answer1>;
+
+    /// Only needs to be implemented when the puzzle has two parts.
+    fn part2(_input: &Self::Input<'_>) -> anyhow::Result<Self::Answer2> {
+        anyhow::bail!("This puzzle has a single part")
+    }
+
+    /// Describes the intermediate data leading to the answer of `part`, for runners to print on
+    /// demand. Puzzles without anything worth showing keep the explanation empty.
+    fn explain(
+        _input: &Self::Input<'_>,
+        _part: u8,
+        _explanation: &mut Explanation,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Answer to a part of a [`Solution`], any printable value except [`Infallible`] which stands for
+/// a part that doesn't exist. Runners count the parts of a puzzle from it, so the answer type and
+/// the number of parts never disagree.
+pub trait Answer: Display {
+    /// Whether the part answered by this type exists.
+    const EXISTS: bool = true;
+}
+
+impl Answer for Infallible {
+    const EXISTS: bool = false;
+}
+
+macro_rules! answer {
+    ($($type:ty),*) => {
+        $(impl Answer for $type {})*
+    };
+}
+
+answer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, char, String);
 
 /// Simple helper function, exists solely to reduce boilerplate caused by having to specify generic
-/// types.
+/// types. Failures are rendered into a [`ParseError`] pointing at the offending line.
 pub fn parse<'i, O>(
     input: &'i str,
     parser: impl Parser<&'i str, O, ErrorTree<&'i str>>,
-) -> Result<O, ErrorTree<Location>> {
-    final_parser(parser)(input)
-}
-
-/// Simple dataless enum with an auto generated nom parser
-///
-/// # Examples
-///
-/// ```
-/// use nom::{error::ParseError, Parser};
-///
-/// common::nom_enum!(
-///     enum Sample {
-///         Children = "children",
-///         Cats = "cats",
-///         Samoyeds = "samoyeds",
-///     }
-/// );
-///
-/// fn puzzle<'i, E: ParseError<&'i str>>(input: &'i str) -> nom::IResult<&'i str, Sample, E> {
-///     Sample::parser().parse(input)
-/// }
-/// ```
-#[macro_export]
-macro_rules! nom_enum {
-    (enum $type:ident {$($variant:ident = $alias:literal,)*}) => {
-        #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-        enum $type {
-            $($variant),*
-        }
-
-        impl $type {
-            fn parser<'i, E: nom::error::ParseError<&'i str>>() -> impl nom::Parser<&'i str, Self, E> {
-                nom::branch::alt((
-                    $(nom::combinator::value(Self::$variant, nom::bytes::complete::tag($alias))),*
-                ))
-            }
-        }
-    };
+) -> Result<O, ParseError> {
+    final_parser(parser)(input).map_err(|tree: ErrorTree<&str>| ParseError::new(input, &tree))
 }
@@ -0,0 +1,111 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// A single line of the puzzle input, remembering where it came from so failures can point at it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'i> {
+    /// 1-based line number.
+    pub number: usize,
+    pub text: &'i str,
+}
+
+impl<'i> Line<'i> {
+    /// Attaches this line to a failure reason.
+    pub fn error(&self, reason: impl Into<anyhow::Error>) -> LineError {
+        LineError {
+            number: self.number,
+            line: self.text.to_owned(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Parses the whole line with [`FromStr`].
+    pub fn parse<T>(&self) -> Result<T, LineError>
+    where
+        T: FromStr,
+        T::Err: Into<anyhow::Error>,
+    {
+        self.text.parse().map_err(|error| self.error(error))
+    }
+}
+
+/// Iterates over the lines of the input, numbering them.
+pub fn lines(input: &str) -> impl '_ + Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(index, text)| Line {
+        number: index + 1,
+        text,
+    })
+}
+
+/// Parses every line of the input with `parser`, stopping at the first line that fails.
+pub fn parse_lines<'i, T>(
+    input: &'i str,
+    mut parser: impl FnMut(&'i str) -> anyhow::Result<T>,
+) -> Result<Vec<T>, LineError> {
+    lines(input)
+        .map(|line| parser(line.text).map_err(|reason| line.error(reason)))
+        .collect()
+}
+
+/// Failure to parse a specific line of the input.
+#[derive(Debug)]
+pub struct LineError {
+    /// 1-based line number.
+    pub number: usize,
+    /// Full text of the offending line.
+    pub line: String,
+    pub reason: anyhow::Error,
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = self.number.to_string().len();
+        writeln!(f, "invalid input on line {}: {}", self.number, self.reason)?;
+        writeln!(f, "{:gutter$} |", "")?;
+        write!(f, "{} | {}", self.number, self.line)
+    }
+}
+
+impl std::error::Error for LineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.reason.source()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context;
+
+    #[test]
+    fn test_parse_lines() -> anyhow::Result<()> {
+        let parsed = crate::parse_lines("1-2\n3-4", |line| {
+            let (a, b) = line.split_once('-').context("Delimiter not found")?;
+            Ok((a.parse::<i64>()?, b.parse::<i64>()?))
+        })?;
+        assert_eq!(parsed, [(1, 2), (3, 4)]);
+
+        let error = crate::parse_lines("1-2\n3-4\n5_6", |line| {
+            line.split_once('-').context("Delimiter not found")
+        })
+        .unwrap_err();
+        assert_eq!(error.number, 3);
+        assert_eq!(error.line, "5_6");
+        assert_eq!(
+            error.to_string(),
+            "invalid input on line 3: Delimiter not found\n  |\n3 | 5_6"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_line_parse() {
+        let mut lines = crate::lines("10\nten");
+        assert_eq!(lines.next().unwrap().parse::<i64>().unwrap(), 10);
+
+        let error = lines.next().unwrap().parse::<i64>().unwrap_err();
+        assert_eq!(error.number, 2);
+        assert_eq!(error.reason.to_string(), "invalid digit found in string");
+    }
+}
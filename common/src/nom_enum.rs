@@ -0,0 +1,256 @@
+use nom::error::ParseError;
+use nom_supreme::tag::TagError;
+use std::fmt::{self, Display};
+
+/// Simple dataless enum with an auto generated nom parser, [`Display`] and [`FromStr`]
+/// implementations and an `ALL` slice listing every variant in declaration order.
+///
+/// Each variant is matched by one or more aliases, the first one being used by [`Display`]. The
+/// generated parser always picks the longest matching alias, and fails expecting any of them.
+/// Placing `#![case_insensitive]` before the enum makes both the parser and [`FromStr`] ignore
+/// ASCII case.
+///
+/// The enum already derives `Clone`, `Copy`, `PartialEq`, `Eq`, `Hash` and `Debug`, other
+/// attributes and doc comments are forwarded as is. The generated methods share the enum's
+/// visibility.
+///
+/// [`FromStr`]: std::str::FromStr
+///
+/// # Examples
+///
+/// ```
+/// use nom::{error::ParseError, Parser};
+/// use nom_supreme::tag::TagError;
+///
+/// common::nom_enum!(
+///     enum Sample {
+///         Children = "children",
+///         Cats = "cats",
+///         Samoyeds = "samoyeds",
+///     }
+/// );
+///
+/// fn puzzle<'i, E>(input: &'i str) -> nom::IResult<&'i str, Sample, E>
+/// where
+///     E: ParseError<&'i str> + TagError<&'i str, &'static str>,
+/// {
+///     Sample::parser().parse(input)
+/// }
+/// ```
+///
+/// ```
+/// common::nom_enum! {
+///     #![case_insensitive]
+///     /// Shapes of rock paper scissors, as written in the strategy guide.
+///     #[derive(PartialOrd, Ord)]
+///     pub enum Shape {
+///         Rock = "A" | "X",
+///         Paper = "B" | "Y",
+///         Scissors = "C" | "Z"
+///     }
+/// }
+///
+/// assert_eq!("y".parse::<Shape>().unwrap(), Shape::Paper);
+/// assert_eq!(Shape::Scissors.to_string(), "C");
+/// assert_eq!(Shape::ALL, [Shape::Rock, Shape::Paper, Shape::Scissors]);
+/// ```
+#[macro_export]
+macro_rules! nom_enum {
+    (#![case_insensitive] $($rest:tt)*) => {
+        $crate::nom_enum!(@impl true; $($rest)*);
+    };
+    (
+        @impl $case_insensitive:literal;
+        $(#[$meta:meta])*
+        $vis:vis enum $type:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $alias:literal $(| $aliases:literal)*
+            ),* $(,)?
+        }
+    ) => {
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        $(#[$meta])*
+        $vis enum $type {
+            $(
+                $(#[$variant_meta])*
+                $variant
+            ),*
+        }
+
+        impl $type {
+            /// Every variant, in declaration order.
+            #[allow(dead_code)]
+            $vis const ALL: &'static [Self] = &[$(Self::$variant),*];
+
+            const ALIASES: &'static [(&'static str, Self)] = &[
+                $(($alias, Self::$variant), $(($aliases, Self::$variant),)*)*
+            ];
+
+            #[allow(dead_code)]
+            $vis fn parser<'i, E>() -> impl nom::Parser<&'i str, Self, E>
+            where
+                E: nom::error::ParseError<&'i str> + nom_supreme::tag::TagError<&'i str, &'static str>,
+            {
+                |input| $crate::__parse_alias(input, Self::ALIASES, $case_insensitive)
+            }
+        }
+
+        impl std::fmt::Display for $type {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(match self {
+                    $(Self::$variant => $alias),*
+                })
+            }
+        }
+
+        impl std::str::FromStr for $type {
+            type Err = $crate::UnknownVariant;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $crate::__find_alias(s, Self::ALIASES, $case_insensitive, stringify!($type))
+            }
+        }
+    };
+    ($(#[$meta:meta])* $vis:vis enum $($rest:tt)*) => {
+        $crate::nom_enum!(@impl false; $(#[$meta])* $vis enum $($rest)*);
+    };
+}
+
+fn matches(candidate: &str, alias: &str, case_insensitive: bool) -> bool {
+    if case_insensitive {
+        candidate.eq_ignore_ascii_case(alias)
+    } else {
+        candidate == alias
+    }
+}
+
+#[doc(hidden)]
+pub fn parse_alias<'i, T: Copy, E: ParseError<&'i str> + TagError<&'i str, &'static str>>(
+    input: &'i str,
+    aliases: &[(&'static str, T)],
+    case_insensitive: bool,
+) -> nom::IResult<&'i str, T, E> {
+    aliases
+        .iter()
+        .filter(|(alias, _)| {
+            input
+                .get(..alias.len())
+                .is_some_and(|prefix| matches(prefix, alias, case_insensitive))
+        })
+        .fold(
+            None,
+            |longest: Option<&(&str, T)>, candidate| match longest {
+                Some(longest) if longest.0.len() >= candidate.0.len() => Some(longest),
+                _ => Some(candidate),
+            },
+        )
+        .map(|(alias, value)| (&input[alias.len()..], *value))
+        .ok_or_else(|| {
+            let mut expected = aliases.iter().map(|(alias, _)| E::from_tag(input, alias));
+            let first = expected.next().expect("An enum has at least one alias");
+            nom::Err::Error(expected.fold(first, E::or))
+        })
+}
+
+#[doc(hidden)]
+pub fn find_alias<T: Copy>(
+    input: &str,
+    aliases: &[(&'static str, T)],
+    case_insensitive: bool,
+    type_name: &'static str,
+) -> Result<T, UnknownVariant> {
+    aliases
+        .iter()
+        .find(|(alias, _)| matches(input, alias, case_insensitive))
+        .map(|(_, value)| *value)
+        .ok_or_else(|| UnknownVariant {
+            type_name,
+            value: input.to_owned(),
+            expected: aliases.iter().map(|(alias, _)| *alias).collect(),
+        })
+}
+
+/// Error returned by the [`FromStr`](std::str::FromStr) implementations generated by
+/// [`nom_enum!`](crate::nom_enum).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownVariant {
+    pub type_name: &'static str,
+    pub value: String,
+    /// Every accepted alias.
+    pub expected: Vec<&'static str>,
+}
+
+impl Display for UnknownVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown {} `{}`, expected one of ",
+            self.type_name, self.value
+        )?;
+        for (index, alias) in self.expected.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "`{alias}`")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownVariant {}
+
+#[cfg(test)]
+mod tests {
+    use nom::{error::Error, Parser};
+
+    crate::nom_enum!(
+        enum Direction {
+            Up = "U" | "up",
+            Down = "D" | "down",
+        }
+    );
+
+    crate::nom_enum! {
+        #![case_insensitive]
+        enum Keyword {
+            Cd = "cd",
+            Cdr = "cdr"
+        }
+    }
+
+    #[test]
+    fn test_parser() {
+        let mut direction = Direction::parser::<Error<&str>>();
+        assert_eq!(direction.parse("up 3").unwrap(), (" 3", Direction::Up));
+        assert_eq!(direction.parse("U 3").unwrap(), (" 3", Direction::Up));
+        assert!(direction.parse("u 3").is_err());
+
+        let mut keyword = Keyword::parser::<Error<&str>>();
+        assert_eq!(keyword.parse("CDR x").unwrap(), (" x", Keyword::Cdr));
+        assert_eq!(keyword.parse("Cd x").unwrap(), (" x", Keyword::Cd));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = crate::parse("left", Direction::parser()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to parse input at line 1, column 1: expected `U`, `up`, `D` or `down`\n  |\n1 | left\n  | ^"
+        );
+    }
+
+    #[test]
+    fn test_display_from_str() {
+        assert_eq!(Direction::ALL, [Direction::Up, Direction::Down]);
+        assert_eq!(Direction::Down.to_string(), "D");
+        assert_eq!("down".parse::<Direction>().unwrap(), Direction::Down);
+        assert_eq!("cDr".parse::<Keyword>().unwrap(), Keyword::Cdr);
+
+        let error = "left".parse::<Direction>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown Direction `left`, expected one of `U`, `up`, `D`, `down`"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use std::borrow::Cow;
+
+/// Byte order mark some Windows editors put at the start of a file.
+const BOM: char = '\u{feff}';
+
+/// Brings a raw puzzle input to the form every parser expects, whatever the editor or system it
+/// was saved with: no byte order mark, LF line endings, no trailing whitespace on any line and a
+/// single final newline, or none for an empty input. Leading whitespace is kept since it can be
+/// meaningful, like the padding of a drawing. Already normalized inputs are borrowed as is.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix(BOM).unwrap_or(input);
+    if is_normalized(input) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut normalized = String::with_capacity(input.len());
+    for line in input.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    Cow::Owned(normalized)
+}
+
+fn is_normalized(input: &str) -> bool {
+    if input.is_empty() {
+        return true;
+    }
+    input
+        .strip_suffix('\n')
+        .is_some_and(|rest| !rest.is_empty() && !rest.ends_with('\n'))
+        && input
+            .lines()
+            .all(|line| line.trim_end().len() == line.len())
+        && !input.contains('\r')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_normalize() {
+        for (raw, expected) in [
+            ("", ""),
+            ("\n", ""),
+            ("\n\n", ""),
+            ("A Y\nB X\n", "A Y\nB X\n"),
+            ("A Y\nB X", "A Y\nB X\n"),
+            ("\u{feff}A Y\r\nB X\r\n", "A Y\nB X\n"),
+            ("$ cd / \n$ ls\t\n\n\n", "$ cd /\n$ ls\n"),
+            ("1000\n  \n2000\n", "1000\n\n2000\n"),
+            ("    [D]    \n[N] [C]    \n", "    [D]\n[N] [C]\n"),
+        ] {
+            assert_eq!(super::normalize(raw), expected, "For input {raw:?}");
+        }
+
+        assert!(matches!(super::normalize("A Y\n"), Cow::Borrowed(_)));
+        assert!(matches!(super::normalize("A Y"), Cow::Owned(_)));
+    }
+}
@@ -0,0 +1,385 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Cheapest known way to a goal, from whichever start it began at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    /// Every node from the start to the goal, both included.
+    pub nodes: Vec<N>,
+    /// Number of steps for unweighted searches, sum of the step costs otherwise.
+    pub cost: C,
+}
+
+/// Result of a search, found or not.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome<N, C> {
+    pub path: Option<Path<N, C>>,
+    /// Number of node expansions, to compare searches or spot a heuristic that doesn't help.
+    pub visited: usize,
+}
+
+/// Every node met so far, numbered in discovery order, along with the node it was reached from.
+struct Tree<N> {
+    nodes: Vec<N>,
+    parents: Vec<Option<usize>>,
+    index: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash> Tree<N> {
+    fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            parents: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Number of `node`, along with whether it was just discovered.
+    fn discover(&mut self, node: N, parent: Option<usize>) -> (usize, bool) {
+        if let Some(&index) = self.index.get(&node) {
+            return (index, false);
+        }
+        let index = self.nodes.len();
+        self.index.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.parents.push(parent);
+        (index, true)
+    }
+
+    /// Nodes from the start leading to `index`, `index` included.
+    fn path(&self, mut index: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[index].clone()];
+        while let Some(parent) = self.parents[index] {
+            path.push(self.nodes[parent].clone());
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth first search from every node of `starts` at once, giving a path with the fewest steps
+/// to the first node satisfying `is_goal`.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Outcome<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut tree = Tree::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let (index, true) = tree.discover(start, None) {
+            queue.push_back((index, 0));
+        }
+    }
+
+    let mut visited = 0;
+    while let Some((index, steps)) = queue.pop_front() {
+        visited += 1;
+        if is_goal(&tree.nodes[index]) {
+            let nodes = tree.path(index);
+            return Outcome {
+                path: Some(Path { nodes, cost: steps }),
+                visited,
+            };
+        }
+
+        for successor in successors(&tree.nodes[index]) {
+            if let (next, true) = tree.discover(successor, Some(index)) {
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    Outcome {
+        path: None,
+        visited,
+    }
+}
+
+/// Depth first search from every node of `starts` in turn, giving the first path found to a
+/// node satisfying `is_goal`. Successors are explored in the order they are given. The path is
+/// not the shortest one, and like a [`bfs`] every node met is remembered, so it only pays off
+/// when goals lie deep along the first branches explored.
+pub fn dfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Outcome<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut tree = Tree::new();
+    let starts: Vec<N> = starts.into_iter().collect();
+    let mut stack: Vec<(N, Option<usize>)> = starts
+        .into_iter()
+        .rev()
+        .map(|start| (start, None))
+        .collect();
+
+    let mut visited = 0;
+    while let Some((node, parent)) = stack.pop() {
+        let (index, true) = tree.discover(node, parent) else {
+            continue;
+        };
+        visited += 1;
+        if is_goal(&tree.nodes[index]) {
+            let nodes = tree.path(index);
+            return Outcome {
+                path: Some(Path {
+                    cost: nodes.len() - 1,
+                    nodes,
+                }),
+                visited,
+            };
+        }
+
+        let next: Vec<N> = successors(&tree.nodes[index]).into_iter().collect();
+        stack.extend(
+            next.into_iter()
+                .rev()
+                .filter(|successor| !tree.index.contains_key(successor))
+                .map(|successor| (successor, Some(index))),
+        );
+    }
+
+    Outcome {
+        path: None,
+        visited,
+    }
+}
+
+/// Cheapest path from any node of `starts` to the first node satisfying `is_goal`, each
+/// successor coming with the cost of the step to it. Costs must not be negative, and
+/// `C::default()` is the cost of staying put.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Outcome<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// [`dijkstra`] guided by `heuristic`, an estimate of the cost left to reach a goal. The path is
+/// the cheapest one as long as the estimate never exceeds the actual cost, like the manhattan
+/// distance on a grid. A node reached again more cheaply after being expanded is expanded again,
+/// which only happens when the estimate drops by more than the cost of a step.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Outcome<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut tree = Tree::new();
+    // Best known cost of each node, indexed like the tree.
+    let mut costs = Vec::new();
+    // Estimated total cost, cost so far and node, cheapest estimate first.
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if let (index, true) = tree.discover(start, None) {
+            costs.push(C::default());
+            heap.push(Reverse((
+                heuristic(&tree.nodes[index]),
+                C::default(),
+                index,
+            )));
+        }
+    }
+
+    let mut visited = 0;
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        if cost > costs[index] {
+            continue;
+        }
+        visited += 1;
+        if is_goal(&tree.nodes[index]) {
+            let nodes = tree.path(index);
+            return Outcome {
+                path: Some(Path { nodes, cost }),
+                visited,
+            };
+        }
+
+        for (successor, step) in successors(&tree.nodes[index]) {
+            let total = cost + step;
+            let (next, new) = tree.discover(successor, Some(index));
+            if new {
+                costs.push(total);
+            } else if total >= costs[next] {
+                continue;
+            } else {
+                costs[next] = total;
+                tree.parents[next] = Some(index);
+            }
+            heap.push(Reverse((total + heuristic(&tree.nodes[next]), total, next)));
+        }
+    }
+
+    Outcome {
+        path: None,
+        visited,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Path;
+    use crate::grid::{Grid, Point};
+    use std::collections::HashMap;
+
+    /// Weighted directed graph, with a cheap detour from `a` to `d`.
+    fn graph() -> HashMap<char, Vec<(char, u32)>> {
+        HashMap::from([
+            ('a', vec![('b', 1), ('d', 10)]),
+            ('b', vec![('c', 2)]),
+            ('c', vec![('d', 3)]),
+            ('d', vec![('e', 1)]),
+            ('e', vec![]),
+            ('f', vec![('e', 1)]),
+        ])
+    }
+
+    fn nodes(path: Option<Path<char, impl Copy>>) -> String {
+        path.map(|path| path.nodes.into_iter().collect())
+            .unwrap_or_default()
+    }
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E
+";
+
+    fn open(maze: &Grid<char>) -> impl '_ + Fn(&Point) -> Vec<Point> {
+        move |&point| {
+            maze.neighbours4(point)
+                .filter(|(_, &cell)| cell != '#')
+                .map(|(point, _)| point)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_graph() {
+        let graph = graph();
+        let successors = |node: &char| graph[node].clone();
+        let unweighted = |node: &char| {
+            graph[node]
+                .iter()
+                .map(|&(next, _)| next)
+                .collect::<Vec<_>>()
+        };
+
+        let outcome = super::dijkstra(['a'], successors, |&node| node == 'e');
+        assert_eq!(outcome.path.as_ref().map(|path| path.cost), Some(7));
+        assert_eq!(nodes(outcome.path), "abcde");
+
+        let outcome = super::bfs(['a'], unweighted, |&node| node == 'e');
+        assert_eq!(outcome.path.as_ref().map(|path| path.cost), Some(2));
+        assert_eq!(nodes(outcome.path), "ade");
+
+        let outcome = super::dfs(['a'], unweighted, |&node| node == 'e');
+        assert_eq!(nodes(outcome.path), "abcde");
+        assert_eq!(outcome.visited, 5);
+
+        let outcome = super::bfs(['a'], unweighted, |&node| node == 'f');
+        assert_eq!(outcome.path, None);
+        assert_eq!(outcome.visited, 5);
+
+        let outcome = super::dijkstra(['c', 'f'], successors, |&node| node == 'e');
+        assert_eq!(nodes(outcome.path), "fe");
+        let outcome = super::bfs(['e'], unweighted, |&node| node == 'e');
+        assert_eq!(outcome.path.map(|path| path.cost), Some(0));
+    }
+
+    #[test]
+    fn test_inconsistent_heuristic() {
+        // Never overestimated, but dropping by 4 from `b` to `c` which are 1 apart, so `c` is
+        // first expanded through the costlier `a`
+        let graph = HashMap::from([
+            ('s', vec![('a', 1), ('b', 1)]),
+            ('a', vec![('c', 3)]),
+            ('b', vec![('c', 1)]),
+            ('c', vec![('g', 3)]),
+            ('g', vec![]),
+        ]);
+        let heuristic = |node: &char| if *node == 'b' { 4 } else { 0 };
+
+        let outcome = super::astar(
+            ['s'],
+            |node| graph[node].clone(),
+            heuristic,
+            |&node| node == 'g',
+        );
+        assert_eq!(outcome.path.as_ref().map(|path| path.cost), Some(5));
+        assert_eq!(nodes(outcome.path), "sbcg");
+    }
+
+    #[test]
+    fn test_grid() -> anyhow::Result<()> {
+        let maze: Grid<char> = MAZE.parse()?;
+        let find = |target| maze.iter().find(|(_, &cell)| cell == target).unwrap().0;
+        let (start, end) = (find('S'), find('E'));
+        let weighted = |point: &Point| {
+            open(&maze)(point)
+                .into_iter()
+                .map(|point| (point, 1))
+                .collect::<Vec<_>>()
+        };
+
+        let bfs = super::bfs([start], open(&maze), |&point| point == end);
+        let dijkstra = super::dijkstra([start], weighted, |&point| point == end);
+        let astar = super::astar(
+            [start],
+            weighted,
+            |point| point.manhattan(end),
+            |&point| point == end,
+        );
+        let dfs = super::dfs([start], open(&maze), |&point| point == end);
+
+        for (nodes, cost) in [
+            bfs.path.map(|path| (path.nodes, path.cost as i64)),
+            dijkstra.path.map(|path| (path.nodes, path.cost)),
+            astar.path.map(|path| (path.nodes, path.cost)),
+        ]
+        .map(Option::unwrap)
+        {
+            assert_eq!(cost, 15);
+            assert_eq!((nodes.len(), nodes[0], nodes[15]), (16, start, end));
+            assert!(nodes.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+        }
+        assert!(astar.visited < dijkstra.visited);
+        assert!(dfs.path.unwrap().cost >= 15);
+
+        // Starting from every open cell of the bottom row, the dead end is right above one of them
+        let bottom: Vec<Point> = (0..6).map(|x| Point::new(x, 4)).collect();
+        let outcome = super::bfs(bottom, open(&maze), |&point| point == Point::new(5, 3));
+        assert_eq!(
+            outcome.path.unwrap().nodes,
+            [Point::new(5, 4), Point::new(5, 3)]
+        );
+        Ok(())
+    }
+}
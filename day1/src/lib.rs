@@ -1,30 +1,77 @@
-pub fn challenge1(input: &str) -> anyhow::Result<i64> {
-    let (acc, max) = input.lines().try_fold((0, 0), |(acc, max), line| {
-        if line.is_empty() {
-            Ok::<_, anyhow::Error>((0, acc.max(max)))
-        } else {
-            let calories: i64 = line.parse()?;
-            Ok((acc + calories, max))
-        }
-    })?;
+use common::{Explanation, Solution};
+
+/// Calories carried by each elf, one entry per item.
+type Elves = Vec<Vec<i64>>;
 
-    Ok(acc.max(max))
+pub fn challenge1(input: &str) -> anyhow::Result<i64> {
+    Day1::part1(&Day1::parse(&common::normalize(input))?)
 }
 
 pub fn challenge2(input: &str) -> anyhow::Result<i64> {
-    let mut total = input.lines().try_fold(vec![0], |mut acc, line| {
-        if line.is_empty() {
-            acc.push(0);
-        } else {
-            let calories: i64 = line.parse()?;
-            *acc.last_mut().unwrap() += calories;
-        }
-        Ok::<_, anyhow::Error>(acc)
-    })?;
+    Day1::part2(&Day1::parse(&common::normalize(input))?)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input<'i> = Elves;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        common::lines(input).try_fold(vec![vec![]], |mut elves, line| {
+            if line.text.is_empty() {
+                elves.push(vec![]);
+            } else {
+                elves.last_mut().unwrap().push(line.parse()?);
+            }
+            Ok(elves)
+        })
+    }
+
+    fn part1(elves: &Self::Input<'_>) -> anyhow::Result<Self::Answer1> {
+        Ok(elves
+            .iter()
+            .map(|calories| calories.iter().sum())
+            .max()
+            .unwrap_or(0))
+    }
 
-    total.sort();
+    fn part2(elves: &Self::Input<'_>) -> anyhow::Result<Self::Answer2> {
+        let mut total: Vec<i64> = elves.iter().map(|calories| calories.iter().sum()).collect();
 
-    Ok(total.into_iter().rev().take(3).sum())
+        total.sort();
+
+        Ok(total.into_iter().rev().take(3).sum())
+    }
+
+    fn explain(
+        elves: &Self::Input<'_>,
+        part: u8,
+        explanation: &mut Explanation,
+    ) -> anyhow::Result<()> {
+        let totals: Vec<(usize, i64)> = (1..)
+            .zip(elves.iter().map(|calories| calories.iter().sum()))
+            .collect();
+
+        explanation.section("Calories per elf", |explanation| {
+            for (elf, total) in &totals {
+                explanation.line(format!("Elf {elf}: {total}"));
+            }
+            Ok(())
+        })?;
+
+        let mut ranked = totals;
+        ranked.sort_by_key(|&(elf, total)| (std::cmp::Reverse(total), elf));
+        let top = if part == 1 { 1 } else { 3 };
+        explanation.section("Carrying the most", |explanation| {
+            for (elf, total) in ranked.into_iter().take(top) {
+                explanation.line(format!("Elf {elf}: {total}"));
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -66,4 +113,19 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_explain() -> anyhow::Result<()> {
+        use common::{Explanation, Solution};
+
+        let elves = crate::Day1::parse(EXAMPLE)?;
+        let mut explanation = Explanation::new();
+        crate::Day1::explain(&elves, 2, &mut explanation)?;
+        assert_eq!(
+            explanation.to_string(),
+            "Calories per elf\n  Elf 1: 6000\n  Elf 2: 4000\n  Elf 3: 11000\n  Elf 4: 24000\n  \
+             Elf 5: 10000\nCarrying the most\n  Elf 4: 24000\n  Elf 3: 11000\n  Elf 5: 10000\n"
+        );
+        Ok(())
+    }
 }
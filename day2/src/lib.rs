@@ -1,56 +1,152 @@
+use anyhow::Context;
+use common::{Explanation, Solution};
+use std::fmt::{self, Display};
+
+common::nom_enum! {
+    /// Opponent's move, first column of the strategy guide.
+    pub enum Shape {
+        Rock = "A",
+        Paper = "B",
+        Scissors = "C",
+    }
+}
+
+common::nom_enum! {
+    /// Second column of the strategy guide, its meaning depends on the part.
+    pub enum Column {
+        X = "X",
+        Y = "Y",
+        Z = "Z",
+    }
+}
+
+type Round = (Shape, Column);
+
+fn round(line: &str) -> anyhow::Result<Round> {
+    let (shape, column) = line
+        .trim()
+        .split_once(' ')
+        .context("Expected two space separated columns")?;
+
+    Ok((shape.parse()?, column.parse()?))
+}
+
 pub fn challenge1(input: &str) -> anyhow::Result<i64> {
-    input.lines().try_fold(0, |acc, line| {
-        // A: Rock
-        // B: Paper
-        // C: Scissors
-        // X: Rock = 1
-        // Y: Paper = 2
-        // Z: Scissors = 3
-        let score = match line.trim() {
-            "A X" => 1 + 3,
-            "A Y" => 2 + 6,
-            "A Z" => 3,
-            "B X" => 1,
-            "B Y" => 2 + 3,
-            "B Z" => 3 + 6,
-            "C X" => 1 + 6,
-            "C Y" => 2,
-            "C Z" => 3 + 3,
-            _ => anyhow::bail!("Unexpected string"),
-        };
-
-        Ok(acc + score)
-    })
+    Day2::part1(&Day2::parse(&common::normalize(input))?)
 }
 
 pub fn challenge2(input: &str) -> anyhow::Result<i64> {
-    input.lines().try_fold(0, |acc, line| {
-        // A: Rock
-        // B: Paper
-        // C: Scissors
-        // X: Loss = 0
-        // Y: Draw = 3
-        // Z: Win = 6
-        let score = match line.trim() {
-            "A X" => 3,
-            "A Y" => 3 + 1,
-            "A Z" => 6 + 2,
-            "B X" => 1,
-            "B Y" => 3 + 2,
-            "B Z" => 6 + 3,
-            "C X" => 2,
-            "C Y" => 3 + 3,
-            "C Z" => 6 + 1,
-            _ => anyhow::bail!("Unexpected string"),
-        };
-
-        Ok(acc + score)
-    })
+    Day2::part2(&Day2::parse(&common::normalize(input))?)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'i> = Vec<Round>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(common::parse_lines(input, round)?)
+    }
+
+    fn part1(rounds: &Self::Input<'_>) -> anyhow::Result<Self::Answer1> {
+        Ok(rounds.iter().map(|round| score(round, 1)).sum())
+    }
+
+    fn part2(rounds: &Self::Input<'_>) -> anyhow::Result<Self::Answer2> {
+        Ok(rounds.iter().map(|round| score(round, 2)).sum())
+    }
+
+    fn explain(
+        rounds: &Self::Input<'_>,
+        part: u8,
+        explanation: &mut Explanation,
+    ) -> anyhow::Result<()> {
+        explanation.section("Rounds", |explanation| {
+            for (number, round) in (1..).zip(rounds) {
+                let played = played(round, part);
+                explanation.line(format!(
+                    "Round {number}: opponent {:?}, played {played:?}, {}, score {}",
+                    round.0,
+                    outcome(round.0, played),
+                    score(round, part)
+                ));
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+}
+
+/// How a round ends for the player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Outcome::Loss => "loss",
+            Outcome::Draw => "draw",
+            Outcome::Win => "win",
+        })
+    }
+}
+
+/// Shape beating `shape`.
+fn winner(shape: Shape) -> Shape {
+    match shape {
+        Shape::Rock => Shape::Paper,
+        Shape::Paper => Shape::Scissors,
+        Shape::Scissors => Shape::Rock,
+    }
+}
+
+/// Shape played in a round. Part 1 reads the second column as the shape to play, part 2 as the
+/// outcome to reach against the opponent.
+fn played(&(opponent, column): &Round, part: u8) -> Shape {
+    match (part, column) {
+        (1, Column::X) => Shape::Rock,
+        (1, Column::Y) => Shape::Paper,
+        (1, Column::Z) => Shape::Scissors,
+        (_, Column::X) => winner(winner(opponent)),
+        (_, Column::Y) => opponent,
+        (_, Column::Z) => winner(opponent),
+    }
+}
+
+fn outcome(opponent: Shape, played: Shape) -> Outcome {
+    if played == opponent {
+        Outcome::Draw
+    } else if played == winner(opponent) {
+        Outcome::Win
+    } else {
+        Outcome::Loss
+    }
+}
+
+/// Value of the shape played plus 0, 3 or 6 for a loss, a draw or a win.
+fn score(round: &Round, part: u8) -> i64 {
+    let played = played(round, part);
+    let shape = match played {
+        Shape::Rock => 1,
+        Shape::Paper => 2,
+        Shape::Scissors => 3,
+    };
+    let outcome = match outcome(round.0, played) {
+        Outcome::Loss => 0,
+        Outcome::Draw => 3,
+        Outcome::Win => 6,
+    };
+    shape + outcome
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = include_str!("../input.txt");
     const SAMPLE: &str = indoc::indoc! {"
         A Y
         B X
@@ -59,10 +155,12 @@ mod tests {
 
     #[test]
     fn test_challenge1() -> anyhow::Result<()> {
+        // Saved on Windows, with a byte order mark and CRLF line endings
+        let windows = format!("\u{feff}{}", SAMPLE.replace('\n', "\r\n"));
         let expected = [
             // Add tests
             (SAMPLE, 15),
-            (INPUT, 14163),
+            (&windows, 15),
         ];
         for (input, output) in expected {
             assert_eq!(crate::challenge1(input)?, output, "For input {}", input)
@@ -75,11 +173,37 @@ mod tests {
         let expected = [
             // Add tests
             (SAMPLE, 12),
-            (INPUT, 12091),
         ];
         for (input, output) in expected {
             assert_eq!(crate::challenge2(input)?, output, "For input {}", input)
         }
         Ok(())
     }
+
+    #[test]
+    fn test_explain() -> anyhow::Result<()> {
+        use common::{Explanation, Solution};
+
+        let rounds = crate::Day2::parse(SAMPLE)?;
+        let mut explanation = Explanation::new();
+        crate::Day2::explain(&rounds, 2, &mut explanation)?;
+        assert_eq!(
+            explanation.to_string(),
+            "Rounds\n  \
+             Round 1: opponent Rock, played Rock, draw, score 4\n  \
+             Round 2: opponent Paper, played Rock, loss, score 1\n  \
+             Round 3: opponent Scissors, played Rock, win, score 7\n"
+        );
+
+        let mut explanation = Explanation::new();
+        crate::Day2::explain(&rounds, 1, &mut explanation)?;
+        assert_eq!(
+            explanation.to_string(),
+            "Rounds\n  \
+             Round 1: opponent Rock, played Paper, win, score 8\n  \
+             Round 2: opponent Paper, played Rock, loss, score 1\n  \
+             Round 3: opponent Scissors, played Scissors, draw, score 6\n"
+        );
+        Ok(())
+    }
 }
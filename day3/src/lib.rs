@@ -1,3 +1,4 @@
+use common::{Explanation, Solution};
 use itertools::Itertools;
 use std::collections::HashSet;
 
@@ -10,33 +11,95 @@ fn score(input: char) -> i64 {
 }
 
 pub fn challenge1(input: &str) -> anyhow::Result<i64> {
-    Ok(input
-        .lines()
-        .map(|line| -> i64 {
-            let (left, right) = line.split_at(line.len() / 2);
-            let left: HashSet<char> = left.chars().collect();
-            let right: HashSet<char> = right.chars().collect();
-
-            left.intersection(&right).copied().map(score).sum()
-        })
-        .sum())
+    Day3::part1(&Day3::parse(&common::normalize(input))?)
 }
 
 pub fn challenge2(input: &str) -> anyhow::Result<i64> {
-    Ok(input
-        .lines()
+    Day3::part2(&Day3::parse(&common::normalize(input))?)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    /// One rucksack per line.
+    type Input<'i> = Vec<&'i str>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        input
+            .lines()
+            .map(|line| {
+                if line.chars().all(|item| item.is_ascii_alphabetic()) {
+                    Ok(line)
+                } else {
+                    anyhow::bail!("Unexpected item in rucksack {line}")
+                }
+            })
+            .collect()
+    }
+
+    fn part1(rucksacks: &Self::Input<'_>) -> anyhow::Result<Self::Answer1> {
+        Ok(rucksacks
+            .iter()
+            .flat_map(|rucksack| misplaced(rucksack))
+            .map(score)
+            .sum())
+    }
+
+    fn part2(rucksacks: &Self::Input<'_>) -> anyhow::Result<Self::Answer2> {
+        Ok(badges(rucksacks).into_iter().flatten().map(score).sum())
+    }
+
+    fn explain(
+        rucksacks: &Self::Input<'_>,
+        part: u8,
+        explanation: &mut Explanation,
+    ) -> anyhow::Result<()> {
+        let (heading, label, shared) = if part == 1 {
+            let shared = rucksacks.iter().map(|rucksack| misplaced(rucksack));
+            ("Items in both compartments", "Rucksack", shared.collect())
+        } else {
+            ("Badges of each group", "Group", badges(rucksacks))
+        };
+
+        explanation.section(heading, |explanation| {
+            for (number, items) in (1..).zip(shared) {
+                let items = items
+                    .into_iter()
+                    .map(|item| format!("{item} (priority {})", score(item)))
+                    .join(", ");
+                explanation.line(format!("{label} {number}: {items}"));
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+}
+
+/// Items found in both compartments of a rucksack, sorted.
+fn misplaced(rucksack: &str) -> Vec<char> {
+    let (left, right) = rucksack.split_at(rucksack.len() / 2);
+    let left: HashSet<char> = left.chars().collect();
+    let right: HashSet<char> = right.chars().collect();
+
+    left.intersection(&right).copied().sorted().collect()
+}
+
+/// Items carried by every elf of each group of three, sorted, one entry per group.
+fn badges(rucksacks: &[&str]) -> Vec<Vec<char>> {
+    rucksacks
+        .iter()
         .map(|line| line.chars().collect::<HashSet<char>>())
         .chunks(3)
         .into_iter()
         .filter_map(|chunks| chunks.reduce(|a, b| a.intersection(&b).copied().collect()))
-        .flatten()
-        .map(score)
-        .sum())
+        .map(|badges| badges.into_iter().sorted().collect())
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = include_str!("../input.txt");
     const SAMPLE: &str = indoc::indoc! {"
         vJrwpWtwJgWrhcsFMMfFFhFp
         jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
@@ -51,14 +114,11 @@ mod tests {
         let expected = [
             // Add tests
             (SAMPLE, 157),
-            (INPUT, 8243),
         ];
         for (input, output) in expected {
             assert_eq!(crate::challenge1(input)?, output, "For input {}", input)
         }
 
-        assert_eq!(crate::challenge1(INPUT)?, 8243);
-
         Ok(())
     }
 
@@ -67,11 +127,32 @@ mod tests {
         let expected = [
             // Add tests
             (SAMPLE, 70),
-            (INPUT, 2631),
         ];
         for (input, output) in expected {
             assert_eq!(crate::challenge2(input)?, output, "For input {}", input)
         }
         Ok(())
     }
+
+    #[test]
+    fn test_explain() -> anyhow::Result<()> {
+        use common::{Explanation, Solution};
+
+        let rucksacks = crate::Day3::parse(SAMPLE)?;
+        let mut explanation = Explanation::new();
+        crate::Day3::explain(&rucksacks, 1, &mut explanation)?;
+        let explanation = explanation.to_string();
+        assert!(
+            explanation.starts_with("Items in both compartments\n  Rucksack 1: p (priority 16)\n")
+        );
+        assert!(explanation.ends_with("  Rucksack 6: s (priority 19)\n"));
+
+        let mut explanation = Explanation::new();
+        crate::Day3::explain(&rucksacks, 2, &mut explanation)?;
+        assert_eq!(
+            explanation.to_string(),
+            "Badges of each group\n  Group 1: r (priority 18)\n  Group 2: Z (priority 52)\n"
+        );
+        Ok(())
+    }
 }
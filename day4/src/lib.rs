@@ -1,51 +1,106 @@
-use anyhow::Context;
-use itertools::Itertools;
+use anyhow::{bail, Context};
+use common::{interval::Interval, Explanation, Solution};
 
-type Range = (i64, i64);
+/// Sections assigned to an elf, both ends included.
+pub type Sections = Interval<i64>;
 
-fn range(input: &str) -> anyhow::Result<Range> {
-    let (min, max) = input.split_once('-').context("Delimiter not found")?;
+fn sections(input: &str) -> anyhow::Result<Sections> {
+    let (min, max) = input
+        .split_once('-')
+        .with_context(|| format!("Delimiter `-` not found in range `{input}`"))?;
 
-    Ok((min.parse()?, max.parse()?))
+    let (min, max): (i64, i64) = (min.parse()?, max.parse()?);
+    if min > max {
+        bail!("Range `{input}` ends before it starts");
+    }
+    Interval::checked_closed(min, max)
+        .with_context(|| format!("Section {max} is past the last one supported"))
 }
 
-fn pair(input: &str) -> anyhow::Result<(Range, Range)> {
-    let (a, b) = input.split_once(',').context("Delimiter not found")?;
-
-    Ok((range(a)?, range(b)?))
-}
+fn pair(input: &str) -> anyhow::Result<(Sections, Sections)> {
+    let (a, b) = input
+        .split_once(',')
+        .context("Delimiter `,` not found between ranges")?;
 
-fn parse(input: &str) -> impl '_ + Iterator<Item = anyhow::Result<(Range, Range)>> {
-    input.lines().map(pair)
+    Ok((sections(a)?, sections(b)?))
 }
 
 pub fn challenge1(input: &str) -> anyhow::Result<i64> {
-    parse(input).fold_ok(0, |acc, (a, b)| {
-        if a.0 <= b.0 && a.1 >= b.1 || b.0 <= a.0 && b.1 >= a.1 {
-            acc + 1
-        } else {
-            acc
-        }
-    })
+    Day4::part1(&Day4::parse(&common::normalize(input))?)
 }
 
 pub fn challenge2(input: &str) -> anyhow::Result<i64> {
-    parse(input).fold_ok(0, |acc, (a, b)| {
-        if a.0 >= b.0 && a.0 <= b.1
-            || a.1 >= b.0 && a.1 <= b.1
-            || b.0 >= a.0 && b.0 <= a.1
-            || b.1 >= a.0 && b.1 <= a.1
-        {
-            acc + 1
+    Day4::part2(&Day4::parse(&common::normalize(input))?)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input<'i> = Vec<(Sections, Sections)>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(common::parse_lines(input, pair)?)
+    }
+
+    fn part1(pairs: &Self::Input<'_>) -> anyhow::Result<Self::Answer1> {
+        Ok(pairs.iter().filter(|(a, b)| nested(a, b)).count() as i64)
+    }
+
+    fn part2(pairs: &Self::Input<'_>) -> anyhow::Result<Self::Answer2> {
+        Ok(pairs.iter().filter(|(a, b)| a.overlaps(b)).count() as i64)
+    }
+
+    fn explain(
+        pairs: &Self::Input<'_>,
+        part: u8,
+        explanation: &mut Explanation,
+    ) -> anyhow::Result<()> {
+        let (heading, relation, matches): (_, _, fn(&Sections, &Sections) -> bool) = if part == 1 {
+            (
+                "Pairs where one range contains the other",
+                "contained",
+                nested,
+            )
         } else {
-            acc
-        }
-    })
+            (
+                "Pairs with overlapping ranges",
+                "overlapping",
+                Sections::overlaps,
+            )
+        };
+
+        explanation.section(heading, |explanation| {
+            for (number, (a, b)) in (1..).zip(pairs) {
+                let verdict = if matches(a, b) { relation } else { "apart" };
+                explanation.line(format!(
+                    "Pair {number}: {},{} {verdict}",
+                    range(a),
+                    range(b)
+                ));
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+}
+
+/// Sections as written in the input, `none` for an empty range which the input never gives.
+fn range(sections: &Sections) -> String {
+    match sections.last() {
+        Some(last) => format!("{}-{last}", sections.start()),
+        None => "none".to_owned(),
+    }
+}
+
+/// Whether either elf's sections include all of the other's.
+fn nested(a: &Sections, b: &Sections) -> bool {
+    a.contains_interval(b) || b.contains_interval(a)
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = include_str!("../input.txt");
     const SAMPLE: &str = indoc::indoc! {"
         2-4,6-8
         2-3,4-5
@@ -60,11 +115,16 @@ mod tests {
         let expected = [
             // Add tests
             (SAMPLE, 2),
-            (INPUT, 424),
         ];
         for (input, output) in expected {
             assert_eq!(crate::challenge1(input)?, output, "For input {}", input)
         }
+        assert!(crate::challenge1("1-9223372036854775807,2-3\n").is_err());
+        let error = crate::challenge1("2-4,6-8\n5-3,6-7\n").unwrap_err();
+        assert_eq!(
+            format!("{error:#}"),
+            "invalid input on line 2: Range `5-3` ends before it starts\n  |\n2 | 5-3,6-7"
+        );
         Ok(())
     }
 
@@ -73,11 +133,32 @@ mod tests {
         let expected = [
             // Add tests
             (SAMPLE, 4),
-            (INPUT, 804),
         ];
         for (input, output) in expected {
             assert_eq!(crate::challenge2(input)?, output, "For input {}", input)
         }
         Ok(())
     }
+
+    #[test]
+    fn test_explain() -> anyhow::Result<()> {
+        use common::{Explanation, Solution};
+
+        let pairs = crate::Day4::parse(SAMPLE)?;
+        let mut explanation = Explanation::new();
+        crate::Day4::explain(&pairs, 2, &mut explanation)?;
+        assert_eq!(
+            explanation.to_string(),
+            indoc::indoc! {"
+                Pairs with overlapping ranges
+                  Pair 1: 2-4,6-8 apart
+                  Pair 2: 2-3,4-5 apart
+                  Pair 3: 5-7,7-9 overlapping
+                  Pair 4: 2-8,3-7 overlapping
+                  Pair 5: 6-6,4-6 overlapping
+                  Pair 6: 2-6,4-8 overlapping
+            "}
+        );
+        Ok(())
+    }
 }
@@ -1,98 +1,202 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, iter};
 
 use anyhow::Context;
+use common::{Explanation, Solution};
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::{anychar, char, digit1, newline, space1},
-    multi::{many1, separated_list1},
-    sequence::{delimited, separated_pair, tuple},
-    Parser,
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, tuple},
+    IResult, Parser,
 };
-use nom_supreme::ParserExt;
-
-pub fn parse(
-    input: &str,
-) -> anyhow::Result<(VecDeque<VecDeque<char>>, Vec<(usize, usize, usize)>)> {
-    let (input_state, moves) = common::parse(
-        input,
-        separated_pair(
-            separated_list1(
-                newline,
+use nom_supreme::{error::ErrorTree, tag::complete::tag, ParserExt};
+
+pub type Stacks = VecDeque<VecDeque<char>>;
+pub type Move = (usize, usize, usize);
+
+/// A single position of the stack diagram, either a crate or an empty slot.
+fn slot(input: &str) -> IResult<&str, Option<char>, ErrorTree<&str>> {
+    alt((
+        delimited(char('['), anychar.cut(), char(']').cut()).map(Some),
+        tag("   ").value(None).context("three spaces"),
+    ))
+    .parse(input)
+}
+
+pub fn challenge1(input: &str) -> anyhow::Result<String> {
+    Day5::part1(&Day5::parse(&common::normalize(input))?)
+}
+
+pub fn challenge2(input: &str) -> anyhow::Result<String> {
+    Day5::part2(&Day5::parse(&common::normalize(input))?)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input<'i> = (Stacks, Vec<Move>);
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        let (input_state, labels, moves) = common::parse(
+            input,
+            tuple((
                 separated_list1(
-                    char(' '),
-                    alt((
-                        delimited(char('['), anychar, char(']')).map(Some),
-                        tag("   ").value(None),
-                    )),
+                    newline,
+                    slot.and(many0(slot.cut().preceded_by(char(' '))))
+                        .map(|(first, rest)| iter::once(first).chain(rest).collect::<Vec<_>>()),
                 ),
-            ),
-            many1(alt((space1, digit1))).delimited_by(newline),
-            separated_list1(
-                newline,
-                tuple((
-                    digit1.parse_from_str::<usize>().preceded_by(tag("move ")),
-                    digit1.parse_from_str::<usize>().preceded_by(tag(" from ")),
-                    digit1.parse_from_str::<usize>().preceded_by(tag(" to ")),
-                )),
-            )
-            .preceded_by(newline),
-        )
-        .terminated(newline.opt()),
-    )?;
-
-    let mut stacks = VecDeque::new();
-    for line in input_state {
-        for (index, item) in line.into_iter().enumerate() {
-            if stacks.len() <= index {
-                stacks.push_back(VecDeque::new());
-            }
-            if let Some(item) = item {
-                stacks[index].push_front(item);
+                // The stack numbers, trailing spaces in the drawing may have been stripped so only
+                // they tell how many stacks there are.
+                many1(alt((space1, digit1))).delimited_by(newline),
+                separated_list1(
+                    newline,
+                    tuple((
+                        digit1
+                            .parse_from_str::<usize>()
+                            .cut()
+                            .preceded_by(tag("move ")),
+                        digit1
+                            .parse_from_str::<usize>()
+                            .preceded_by(tag(" from "))
+                            .cut(),
+                        digit1
+                            .parse_from_str::<usize>()
+                            .preceded_by(tag(" to "))
+                            .cut(),
+                    )),
+                )
+                .preceded_by(newline),
+            ))
+            .terminated(newline.opt()),
+        )?;
+
+        let count = labels
+            .iter()
+            .filter(|label| !label.trim().is_empty())
+            .count();
+        let mut stacks: Stacks = iter::repeat_with(VecDeque::new).take(count).collect();
+        for line in input_state {
+            for (index, item) in line.into_iter().enumerate() {
+                if let Some(item) = item {
+                    stacks
+                        .get_mut(index)
+                        .with_context(|| {
+                            format!("Crate {item} is past the last of {count} stacks")
+                        })?
+                        .push_front(item);
+                }
             }
         }
+
+        tracing::debug!(
+            stacks = stacks.len(),
+            moves = moves.len(),
+            "parsed the drawing"
+        );
+        Ok((stacks, moves))
     }
 
-    Ok((stacks, moves))
-}
+    fn part1((stacks, moves): &Self::Input<'_>) -> anyhow::Result<Self::Answer1> {
+        let mut stacks = stacks.clone();
 
-pub fn challenge1(input: &str) -> anyhow::Result<String> {
-    let (mut stacks, moves) = parse(input)?;
+        for &mv in moves {
+            apply(&mut stacks, mv, Crane::OneAtATime)?;
+        }
+
+        Ok(tops(stacks))
+    }
 
-    for (count, from, to) in moves {
-        println!("moving {count} from {from} to {to} : {stacks:?}");
-        for _ in 0..count {
-            let tmp = stacks[from - 1].pop_back().context("Stack was empty")?;
-            stacks[to - 1].push_back(tmp);
+    fn part2((stacks, moves): &Self::Input<'_>) -> anyhow::Result<Self::Answer2> {
+        let mut stacks = stacks.clone();
+
+        for &mv in moves {
+            apply(&mut stacks, mv, Crane::AtOnce)?;
         }
+
+        Ok(tops(stacks))
     }
 
-    Ok(stacks
-        .into_iter()
-        .filter_map(|mut stack| stack.pop_back())
-        .collect())
+    fn explain(
+        (stacks, moves): &Self::Input<'_>,
+        part: u8,
+        explanation: &mut Explanation,
+    ) -> anyhow::Result<()> {
+        let crane = if part == 1 {
+            Crane::OneAtATime
+        } else {
+            Crane::AtOnce
+        };
+        let mut stacks = stacks.clone();
+
+        explanation.section("Starting stacks", |explanation| {
+            explanation.line(drawing(&stacks));
+            Ok(())
+        })?;
+        for &mv in moves {
+            let (count, from, to) = mv;
+            apply(&mut stacks, mv, crane)?;
+            explanation.section(
+                format!("After move {count} from {from} to {to}"),
+                |explanation| {
+                    explanation.line(drawing(&stacks));
+                    Ok(())
+                },
+            )?;
+        }
+        Ok(())
+    }
 }
 
-pub fn challenge2(input: &str) -> anyhow::Result<String> {
-    let (mut stacks, moves) = parse(input)?;
+/// How the crane moves several crates.
+#[derive(Clone, Copy, Debug)]
+enum Crane {
+    OneAtATime,
+    AtOnce,
+}
 
-    for (count, from, to) in moves {
-        println!("moving {count} from {from} to {to} : {stacks:?}");
-        let pivot = stacks[from - 1].len() - count;
-        while let Some(item) = stacks[from - 1].remove(pivot) {
-            stacks[to - 1].push_back(item);
+fn apply(stacks: &mut Stacks, (count, from, to): Move, crane: Crane) -> anyhow::Result<()> {
+    match crane {
+        Crane::OneAtATime => {
+            tracing::trace!(count, from, to, ?stacks, "moving crates one at a time");
+            for _ in 0..count {
+                let tmp = stacks[from - 1].pop_back().context("Stack was empty")?;
+                stacks[to - 1].push_back(tmp);
+            }
+        }
+        Crane::AtOnce => {
+            tracing::trace!(count, from, to, ?stacks, "moving crates at once");
+            let pivot = stacks[from - 1].len() - count;
+            while let Some(item) = stacks[from - 1].remove(pivot) {
+                stacks[to - 1].push_back(item);
+            }
         }
     }
+    Ok(())
+}
 
-    Ok(stacks
+fn tops(stacks: Stacks) -> String {
+    stacks
         .into_iter()
         .filter_map(|mut stack| stack.pop_back())
-        .collect())
+        .collect()
+}
+
+/// One line per stack, from bottom to top.
+fn drawing(stacks: &Stacks) -> String {
+    (1..)
+        .zip(stacks)
+        .map(|(number, stack)| {
+            let crates: String = stack.iter().map(|item| format!(" [{item}]")).collect();
+            format!("{number}:{crates}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = include_str!("../input.txt");
     const SAMPLE: &str = indoc::indoc! {"
             [D]    
         [N] [C]    
@@ -106,10 +210,17 @@ mod tests {
 
     #[test]
     fn test_challenge1() -> anyhow::Result<()> {
+        // Saved on Windows, with a byte order mark and CRLF line endings
+        let windows = format!("\u{feff}{}", SAMPLE.replace('\n', "\r\n"));
         let expected = [
             // Add tests
             (SAMPLE, "CMZ"),
-            (INPUT, "RTGWZTHLD"),
+            (&windows, "CMZ"),
+            // The last stacks are empty, only the stack numbers tell they exist
+            (
+                "[A]        \n[B]        \n 1   2   3 \n\nmove 1 from 1 to 3",
+                "BA",
+            ),
         ];
         for (input, output) in expected {
             assert_eq!(
@@ -127,11 +238,43 @@ mod tests {
         let expected = [
             // Add tests
             (SAMPLE, "MCD"),
-            (INPUT, "STHGRZZFR"),
         ];
         for (input, output) in expected {
             assert_eq!(crate::challenge2(input)?, output, "For input {}", input)
         }
         Ok(())
     }
+
+    #[test]
+    fn test_explain() -> anyhow::Result<()> {
+        use common::{Explanation, Solution};
+
+        let input = crate::Day5::parse(SAMPLE)?;
+        let mut explanation = Explanation::new();
+        crate::Day5::explain(&input, 1, &mut explanation)?;
+        let explanation = explanation.to_string();
+        assert!(explanation.starts_with(indoc::indoc! {"
+            Starting stacks
+              1: [Z] [N]
+              2: [M] [C] [D]
+              3: [P]
+            After move 1 from 2 to 1
+              1: [Z] [N] [D]
+              2: [M] [C]
+              3: [P]
+        "}));
+        assert!(explanation.ends_with(indoc::indoc! {"
+            After move 1 from 1 to 2
+              1: [C]
+              2: [M]
+              3: [P] [D] [N] [Z]
+        "}));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse() {
+        let error = crate::challenge1("[A] [B]\n 1 \n\nmove 1 from 1 to 2\n").unwrap_err();
+        assert_eq!(error.to_string(), "Crate B is past the last of 1 stacks");
+    }
 }
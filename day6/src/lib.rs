@@ -1,4 +1,5 @@
 use anyhow::Context;
+use common::{Explanation, Solution};
 use itertools::Itertools;
 
 fn find_marker<const SIZE: usize>(input: &str) -> Option<usize> {
@@ -19,16 +20,62 @@ fn find_marker<const SIZE: usize>(input: &str) -> Option<usize> {
 }
 
 pub fn challenge1(input: &str) -> anyhow::Result<usize> {
-    find_marker::<4>(input).context("No marker found")
+    Day6::part1(&Day6::parse(&common::normalize(input))?)
 }
 
 pub fn challenge2(input: &str) -> anyhow::Result<usize> {
-    find_marker::<14>(input).context("No marker found")
+    Day6::part2(&Day6::parse(&common::normalize(input))?)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    /// The datastream buffer.
+    type Input<'i> = &'i str;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(input.trim_end())
+    }
+
+    fn part1(buffer: &Self::Input<'_>) -> anyhow::Result<Self::Answer1> {
+        find_marker::<4>(buffer).context("No marker found")
+    }
+
+    fn part2(buffer: &Self::Input<'_>) -> anyhow::Result<Self::Answer2> {
+        find_marker::<14>(buffer).context("No marker found")
+    }
+
+    fn explain(
+        buffer: &Self::Input<'_>,
+        part: u8,
+        explanation: &mut Explanation,
+    ) -> anyhow::Result<()> {
+        let (size, end) = if part == 1 {
+            (4, find_marker::<4>(buffer))
+        } else {
+            (14, find_marker::<14>(buffer))
+        };
+
+        match end {
+            Some(end) => explanation.line(format!(
+                "Characters {} to {end} are {size} different ones: {}",
+                end - size + 1,
+                buffer
+                    .chars()
+                    .skip(end - size)
+                    .take(size)
+                    .collect::<String>()
+            )),
+            None => explanation.line(format!("No {size} consecutive characters are different")),
+        };
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = include_str!("../input.txt");
     const SAMPLE1: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
     const SAMPLE2: &str = "bvwbjplbgvbhsrlpgdmjqwftvncz";
     const SAMPLE3: &str = "nppdvjthqldpwncqszvftbrmjlhg";
@@ -44,7 +91,6 @@ mod tests {
             (SAMPLE3, 6),
             (SAMPLE4, 10),
             (SAMPLE5, 11),
-            (INPUT, 1566),
         ];
         for (input, output) in expected {
             assert_eq!(crate::challenge1(input)?, output, "For input {}", input)
@@ -61,11 +107,34 @@ mod tests {
             (SAMPLE3, 23),
             (SAMPLE4, 29),
             (SAMPLE5, 26),
-            (INPUT, 2265),
         ];
         for (input, output) in expected {
             assert_eq!(crate::challenge2(input)?, output, "For input {}", input)
         }
         Ok(())
     }
+
+    #[test]
+    fn test_explain() -> anyhow::Result<()> {
+        use common::{Explanation, Solution};
+
+        let explain = |input, part| -> anyhow::Result<String> {
+            let mut explanation = Explanation::new();
+            crate::Day6::explain(&crate::Day6::parse(input)?, part, &mut explanation)?;
+            Ok(explanation.to_string())
+        };
+        assert_eq!(
+            explain(SAMPLE1, 1)?,
+            "Characters 4 to 7 are 4 different ones: jpqm\n"
+        );
+        assert_eq!(
+            explain(SAMPLE1, 2)?,
+            "Characters 6 to 19 are 14 different ones: qmgbljsphdztnv\n"
+        );
+        assert_eq!(
+            explain("aaaa", 1)?,
+            "No 4 consecutive characters are different\n"
+        );
+        Ok(())
+    }
 }
@@ -1,11 +1,13 @@
 use std::{borrow::Cow, collections::HashMap, iter};
 
-use anyhow::{bail, Context};
+use anyhow::{anyhow, Context};
+use common::{Explanation, Solution};
 use itertools::Itertools;
 
-type Directory<'a> = HashMap<Cow<'a, str>, INode<'a>>;
+pub type Directory<'a> = HashMap<Cow<'a, str>, INode<'a>>;
 
-enum INode<'a> {
+/// Node of the file system tree rebuilt from the terminal output.
+pub enum INode<'a> {
     File(usize),
     Directory(Directory<'a>),
 }
@@ -29,7 +31,7 @@ impl<'a> INode<'a> {
     fn total_size(&self) -> usize {
         match self {
             Self::File(size) => *size,
-            Self::Directory(dirs) => dirs.iter().map(|(_, node)| node.total_size()).sum(),
+            Self::Directory(dirs) => dirs.values().map(|node| node.total_size()).sum(),
         }
     }
 
@@ -40,37 +42,55 @@ impl<'a> INode<'a> {
         Box::new(iter::once(self).chain(dirs.values().flat_map(INode::recurse_dirs)))
     }
 
+    /// Adds the size of this directory as a section holding its subdirectories, sorted by name.
+    fn explain_sizes(&self, name: &str, explanation: &mut Explanation) -> anyhow::Result<()> {
+        let INode::Directory(dirs) = self else {
+            return Ok(());
+        };
+        explanation.section(format!("{name} {}", self.total_size()), |explanation| {
+            for (name, node) in dirs.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
+                node.explain_sizes(name, explanation)?;
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
     fn from_cli_history(input: &'a str) -> anyhow::Result<Self> {
-        let mut lines = input.lines().peekable();
+        let mut lines = common::lines(input).peekable();
         let mut root = INode::Directory(Default::default());
         let mut path: Vec<&str> = Vec::new();
 
         while let Some(line) = lines.next() {
-            match line {
+            match line.text {
                 "$ cd /" => path.clear(),
                 "$ cd .." => {
                     path.pop();
                 }
                 "$ ls" => {
-                    let dir = root.get_mut_dir(path.iter()).context("Non existant path")?;
-                    for line in lines.peeking_take_while(|line| !line.starts_with('$')) {
-                        if line.starts_with("dir ") {
-                            let (_, name) = line.split_at(4);
+                    let dir = root.get_mut_dir(path.iter()).ok_or_else(|| {
+                        line.error(anyhow!("Non existant path /{}", path.join("/")))
+                    })?;
+                    for line in lines.peeking_take_while(|line| !line.text.starts_with('$')) {
+                        if let Some(name) = line.text.strip_prefix("dir ") {
                             dir.insert(Cow::Borrowed(name), INode::Directory(Default::default()));
-                        } else if let Some((size, name)) = line.split_once(' ') {
-                            let size: usize = size.parse().context("Invalid file size")?;
+                        } else if let Some((size, name)) = line.text.split_once(' ') {
+                            let size: usize = size
+                                .parse()
+                                .context("Invalid file size")
+                                .map_err(|error| line.error(error))?;
                             dir.insert(Cow::Borrowed(name), INode::File(size));
                         } else {
-                            bail!("Unexpected command output {line}");
+                            return Err(line.error(anyhow!("Unexpected command output")).into());
                         }
                     }
                 }
-                line if line.starts_with("$ cd ") => {
-                    let (_, name) = line.split_at(5);
+                text if text.starts_with("$ cd ") => {
+                    let (_, name) = text.split_at(5);
                     path.push(name);
                 }
                 _ => {
-                    bail!("Unexpected line format {line}");
+                    return Err(line.error(anyhow!("Unexpected line format")).into());
                 }
             }
         }
@@ -80,29 +100,64 @@ impl<'a> INode<'a> {
 }
 
 pub fn challenge1(input: &str) -> anyhow::Result<usize> {
-    Ok(INode::from_cli_history(input)?
-        .recurse_dirs()
-        .map(|dir| dir.total_size())
-        .filter(|size| *size < 100000)
-        .sum())
+    Day7::part1(&Day7::parse(&common::normalize(input))?)
 }
 
 pub fn challenge2(input: &str) -> anyhow::Result<usize> {
-    let root = INode::from_cli_history(input)?;
+    Day7::part2(&Day7::parse(&common::normalize(input))?)
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input<'i> = INode<'i>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        INode::from_cli_history(input)
+    }
 
-    let free = 70000000 - root.total_size();
-    let min = 30000000 - free;
+    fn part1(root: &Self::Input<'_>) -> anyhow::Result<Self::Answer1> {
+        Ok(root
+            .recurse_dirs()
+            .map(|dir| dir.total_size())
+            .filter(|size| *size < 100000)
+            .sum())
+    }
+
+    fn part2(root: &Self::Input<'_>) -> anyhow::Result<Self::Answer2> {
+        let free = 70000000 - root.total_size();
+        let min = 30000000 - free;
+
+        root.recurse_dirs()
+            .map(|dir| dir.total_size())
+            .filter(|size| *size >= min)
+            .min()
+            .context("No directory big enough")
+    }
 
-    root.recurse_dirs()
-        .map(|dir| dir.total_size())
-        .filter(|size| *size >= min)
-        .min()
-        .context("No directory big enough")
+    fn explain(
+        root: &Self::Input<'_>,
+        part: u8,
+        explanation: &mut Explanation,
+    ) -> anyhow::Result<()> {
+        explanation.section("Directory sizes", |explanation| {
+            root.explain_sizes("/", explanation)
+        })?;
+        if part == 2 {
+            let free = 70000000usize.saturating_sub(root.total_size());
+            explanation.line(format!(
+                "{free} unused, {} more needed",
+                30000000usize.saturating_sub(free)
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    const INPUT: &str = include_str!("../input.txt");
     const SAMPLE: &str = indoc::indoc! {"
         $ cd /
         $ ls
@@ -131,10 +186,12 @@ mod tests {
 
     #[test]
     fn test_challenge1() -> anyhow::Result<()> {
+        // Saved on Windows, with a byte order mark and CRLF line endings
+        let windows = format!("\u{feff}{}", SAMPLE.replace('\n', "\r\n"));
         let expected = [
             // Add tests
             (SAMPLE, 95437),
-            (INPUT, 1084134),
+            (&windows, 95437),
         ];
 
         for (input, output) in expected {
@@ -148,14 +205,33 @@ mod tests {
         let expected = [
             // Add tests
             (SAMPLE, 24933642),
-            (INPUT, 6183184),
         ];
 
         for (input, output) in expected {
             assert_eq!(crate::challenge2(input)?, output, "For input {}", input)
         }
 
-        assert!(crate::challenge2(INPUT)? < 25622272);
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain() -> anyhow::Result<()> {
+        use common::{Explanation, Solution};
+
+        let root = crate::Day7::parse(SAMPLE)?;
+        let mut explanation = Explanation::new();
+        crate::Day7::explain(&root, 2, &mut explanation)?;
+        assert_eq!(
+            explanation.to_string(),
+            indoc::indoc! {"
+                Directory sizes
+                  / 48381165
+                    a 94853
+                      e 584
+                    d 24933642
+                21618835 unused, 8381165 more needed
+            "}
+        );
         Ok(())
     }
 }
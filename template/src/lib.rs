@@ -1,14 +1,37 @@
-pub fn challenge1(_input: &str) -> anyhow::Result<i64> {
-    Ok(0)
+use common::Solution;
+
+pub fn challenge1(input: &str) -> anyhow::Result<<Template as Solution>::Answer1> {
+    Template::part1(&Template::parse(&common::normalize(input))?)
+}
+
+pub fn challenge2(input: &str) -> anyhow::Result<<Template as Solution>::Answer2> {
+    Template::part2(&Template::parse(&common::normalize(input))?)
 }
 
-pub fn challenge2(_input: &str) -> anyhow::Result<i64> {
-    Ok(0)
+pub struct Template;
+
+// Answers can be any `common::Answer` type, which covers numbers and strings. A single part
+// puzzle sets `Answer2 = Infallible` and leaves out `part2`.
+impl Solution for Template {
+    type Input<'i> = &'i str;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input<'_>> {
+        Ok(input)
+    }
+
+    fn part1(_input: &Self::Input<'_>) -> anyhow::Result<Self::Answer1> {
+        Ok(0)
+    }
+
+    fn part2(_input: &Self::Input<'_>) -> anyhow::Result<Self::Answer2> {
+        Ok(0)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    // const INPUT: &str = include_str!("../input.txt");
     const SAMPLE: &str = indoc::indoc! {"
     "};
 
@@ -16,11 +39,15 @@ mod tests {
     fn test_challenge1() -> anyhow::Result<()> {
         let expected = [
             // Add tests
-            (SAMPLE, 0),
-            // (INPUT, 0),
+            (SAMPLE, "0"),
         ];
         for (input, output) in expected {
-            assert_eq!(crate::challenge1(input)?, output, "For input {}", input)
+            assert_eq!(
+                crate::challenge1(input)?.to_string(),
+                output,
+                "For input {}",
+                input
+            )
         }
         Ok(())
     }
@@ -29,11 +56,15 @@ mod tests {
     fn test_challenge2() -> anyhow::Result<()> {
         let expected = [
             // Add tests
-            (SAMPLE, 0),
-            // (INPUT, 0),
+            (SAMPLE, "0"),
         ];
         for (input, output) in expected {
-            assert_eq!(crate::challenge2(input)?, output, "For input {}", input)
+            assert_eq!(
+                crate::challenge2(input)?.to_string(),
+                output,
+                "For input {}",
+                input
+            )
         }
         Ok(())
     }